@@ -0,0 +1,445 @@
+use crate::menu;
+use n1_project::{
+    csv,
+    graph_exporter::{self, Backend, DotFormat, DotOptions},
+    graph_importer,
+    svg_renderer::Layout,
+    Attributes, Edge, Graph, GraphError, Node, PathMode, Weight,
+};
+use std::{
+    fs,
+    path::{Path, PathBuf},
+    process::ExitCode,
+};
+
+/// `Ok(true)` exits with 0, `Ok(false)` with 1 (e.g. no path found) and
+/// `Err` with 2, after printing the message to stderr
+type CommandResult = Result<bool, String>;
+
+const USAGE: &str = "\
+Uso: n1_project <comando> <arquivo do grafo> [argumentos]
+
+O arquivo pode ser JSON, GraphML (.graphml), GEXF (.gexf) ou DOT (.dot, .gv),
+que só pode ser lido.
+
+Sem argumentos o menu interativo é aberto com o arquivo ./graph.json,
+use `n1_project -f <arquivo>` para abri-lo com outro arquivo.
+
+Comandos:
+    path <arquivo> <origem> <destino> [--bfs]    Exibe um caminho entre dois vértices
+    cost <arquivo> <origem> <destino>            Exibe o custo e o menor caminho entre dois vértices
+    adjacent <arquivo> <vértice 1> <vértice 2>   Verifica se dois vértices são adjacentes
+    complete <arquivo>                           Verifica se o grafo é completo
+    export <arquivo> [saída] [opções]            Exporta o grafo como SVG, ou com o graphviz usando --dot
+    add-edge <arquivo> <origem> <destino> [peso] Cria uma aresta e salva o grafo
+    convert <arquivo> <saída>                    Salva o grafo no formato da extensão da saída
+    import-csv <vértices.csv> [arestas.csv] <saída> [--directed]
+                                                 Cria o grafo a partir das listas de vértices e arestas
+    import-matrix <matriz.csv> [vértices.csv] <saída> [--directed] [--weighted]
+                                                 Cria o grafo a partir da matriz de adjacência
+    export-csv <arquivo>                         Exporta as listas de vértices e arestas
+    export-matrix <arquivo> [saída]              Exporta a matriz de adjacência
+    help                                         Exibe essa mensagem
+
+Opções do export:
+    --circular                 Posiciona os vértices em círculo no SVG
+    --dot[=png|svg|pdf]        Gera a imagem com o graphviz, em PNG por padrão
+    --types                    Cor e formato dos vértices pelo tipo do local (graphviz)
+    --scale                    Arestas mais pesadas mais longas e grossas (graphviz)
+    --path=<origem>,<destino>  Destaca o menor caminho entre dois vértices
+    --mst                      Destaca a árvore geradora mínima
+
+Códigos de saída:
+    0  Sucesso, ou verificação positiva
+    1  Verificação negativa, ou caminho não encontrado
+    2  Erro nos argumentos ou ao ler/escrever arquivos";
+
+pub fn run(args: &[String]) -> ExitCode {
+    let result = match args.split_first() {
+        Some((command, args)) => match command.as_str() {
+            "path" => path(args),
+            "cost" => cost(args),
+            "adjacent" => adjacent(args),
+            "complete" => complete(args),
+            "export" => export(args),
+            "add-edge" => add_edge(args),
+            "convert" => convert(args),
+            "import-csv" => import_csv(args),
+            "import-matrix" => import_matrix(args),
+            "export-csv" => export_csv(args),
+            "export-matrix" => export_matrix(args),
+            "help" | "--help" | "-h" => {
+                println!("{USAGE}");
+                Ok(true)
+            }
+            _ => Err(format!("Comando desconhecido: {command}\n\n{USAGE}")),
+        },
+        None => Err(USAGE.to_string()),
+    };
+
+    match result {
+        Ok(true) => ExitCode::SUCCESS,
+        Ok(false) => ExitCode::from(1),
+        Err(err) => {
+            eprintln!("{err}");
+            ExitCode::from(2)
+        }
+    }
+}
+
+fn path(args: &[String]) -> CommandResult {
+    let [file, from, to, options @ ..] = args else {
+        return Err(usage("path <arquivo> <origem> <destino> [--bfs]"));
+    };
+
+    let mode = match options {
+        [] => PathMode::Depth,
+        [option] if option == "--bfs" => PathMode::Breadth,
+        _ => return Err(usage("path <arquivo> <origem> <destino> [--bfs]")),
+    };
+
+    let graph = load_graph(file)?;
+    let from = find_node(&graph, from)?;
+    let to = find_node(&graph, to)?;
+
+    match graph.get_path(from, to, mode) {
+        Ok(path) => {
+            println!("{}", menu::get_string_path(path, graph.is_directed()));
+            Ok(true)
+        }
+        Err(err @ GraphError::NoPath { .. }) => {
+            println!("{err}");
+            Ok(false)
+        }
+        Err(err) => Err(err.to_string()),
+    }
+}
+
+fn cost(args: &[String]) -> CommandResult {
+    let [file, from, to] = args else {
+        return Err(usage("cost <arquivo> <origem> <destino>"));
+    };
+
+    let graph = load_graph(file)?;
+
+    if !graph.is_weighted() {
+        return Err("O grafo precisa ser ponderado".to_string());
+    }
+
+    let from = find_node(&graph, from)?;
+    let to = find_node(&graph, to)?;
+
+    match graph.calculate_path(from, to) {
+        Ok((cost, path)) => {
+            println!("{cost}");
+            println!("{}", menu::get_string_path(path, graph.is_directed()));
+            Ok(true)
+        }
+        Err(err @ GraphError::NoPath { .. }) => {
+            println!("{err}");
+            Ok(false)
+        }
+        Err(err) => Err(err.to_string()),
+    }
+}
+
+fn adjacent(args: &[String]) -> CommandResult {
+    let [file, node1, node2] = args else {
+        return Err(usage("adjacent <arquivo> <vértice 1> <vértice 2>"));
+    };
+
+    let graph = load_graph(file)?;
+    let node1 = find_node(&graph, node1)?;
+    let node2 = find_node(&graph, node2)?;
+
+    let is_adjacent = graph.is_adjacent(node1, node2);
+    println!("{}", if is_adjacent { "sim" } else { "não" });
+
+    Ok(is_adjacent)
+}
+
+fn complete(args: &[String]) -> CommandResult {
+    let [file] = args else {
+        return Err(usage("complete <arquivo>"));
+    };
+
+    let graph = load_graph(file)?;
+
+    let is_complete = graph.is_complete();
+    println!("{}", if is_complete { "sim" } else { "não" });
+
+    Ok(is_complete)
+}
+
+fn export(args: &[String]) -> CommandResult {
+    let command = "export <arquivo> [saída] [opções]";
+
+    let (options, positional): (Vec<&String>, Vec<&String>) =
+        args.iter().partition(|arg| arg.starts_with("--"));
+
+    let [file, output @ ..] = positional.as_slice() else {
+        return Err(usage(command));
+    };
+
+    let graph = load_graph(file)?;
+
+    let mut layout = Layout::ForceDirected;
+    let mut dot_options = None;
+    let mut style = DotOptions::default();
+    let mut highlight = None;
+
+    for option in options {
+        match option.split_once('=') {
+            None if option == "--circular" => layout = Layout::Circular,
+            None if option == "--dot" => dot_options = Some(DotFormat::Png),
+            Some(("--dot", format)) => dot_options = Some(parse_dot_format(format)?),
+            None if option == "--types" => {
+                style.color_by_type = true;
+                style.shape_by_type = true;
+            }
+            None if option == "--scale" => style.scale_by_weight = true,
+            Some(("--path", nodes)) => {
+                let Some((from, to)) = nodes.split_once(',') else {
+                    return Err(usage(command));
+                };
+
+                highlight = Some(highlighted_path(&graph, from, to)?);
+            }
+            None if option == "--mst" => {
+                let (tree, _) = graph
+                    .minimum_spanning_tree()
+                    .map_err(|err| err.to_string())?;
+                highlight = Some(tree);
+            }
+            _ => return Err(format!("Opção desconhecida: {option}\n\n{USAGE}")),
+        }
+    }
+
+    let backend = match dot_options {
+        Some(format) => Backend::Graphviz(DotOptions { format, ..style }),
+        None => Backend::Svg(layout),
+    };
+
+    let output = match output {
+        [] => graph_exporter::output_path(Path::new(file), backend),
+        [output] => PathBuf::from(output),
+        _ => return Err(usage(command)),
+    };
+
+    match graph_exporter::export_graph(&graph, highlight.as_ref(), &output, backend) {
+        Ok(_) => {
+            println!("{}", output.display());
+            Ok(true)
+        }
+        Err(err) => Err(format!("Erro ao exportar grafo: {err}")),
+    }
+}
+
+fn import_csv(args: &[String]) -> CommandResult {
+    let command = "import-csv <vértices.csv> [arestas.csv] <saída> [--directed]";
+
+    let (is_directed, args) = match args {
+        [args @ .., option] if option == "--directed" => (true, args),
+        args => (false, args),
+    };
+
+    let (nodes, edges, output) = match args {
+        [nodes, output] => (nodes, None, output),
+        [nodes, edges, output] => (nodes, Some(edges), output),
+        _ => return Err(usage(command)),
+    };
+
+    let nodes = read_file(nodes)?;
+    let edges = match edges {
+        Some(edges) => Some(read_file(edges)?),
+        None => None,
+    };
+
+    let graph = csv::from_csv(&nodes, edges.as_deref(), is_directed)
+        .map_err(|err| format!("Erro ao ler o CSV: {err}"))?;
+
+    write_graph(&graph, output)
+}
+
+fn import_matrix(args: &[String]) -> CommandResult {
+    let command = "import-matrix <matriz.csv> [vértices.csv] <saída> [--directed] [--weighted]";
+
+    let (mut is_directed, mut is_weighted) = (false, false);
+    let mut args = args;
+
+    while let [rest @ .., option] = args {
+        match option.as_str() {
+            "--directed" => is_directed = true,
+            "--weighted" => is_weighted = true,
+            _ => break,
+        }
+
+        args = rest;
+    }
+
+    let (matrix, nodes, output) = match args {
+        [matrix, output] => (matrix, None, output),
+        [matrix, nodes, output] => (matrix, Some(nodes), output),
+        _ => return Err(usage(command)),
+    };
+
+    let matrix = read_file(matrix)?;
+    let nodes = match nodes {
+        Some(nodes) => Some(read_file(nodes)?),
+        None => None,
+    };
+
+    let graph = csv::from_adjacency_matrix(&matrix, nodes.as_deref(), is_directed, is_weighted)
+        .map_err(|err| format!("Erro ao ler o CSV: {err}"))?;
+
+    write_graph(&graph, output)
+}
+
+fn export_csv(args: &[String]) -> CommandResult {
+    let [file] = args else {
+        return Err(usage("export-csv <arquivo>"));
+    };
+
+    let graph = load_graph(file)?;
+    let (nodes_path, edges_path) = csv::list_paths(Path::new(file));
+
+    write_file(&nodes_path, &csv::nodes_to_csv(&graph))?;
+    write_file(&edges_path, &csv::edges_to_csv(&graph))?;
+
+    Ok(true)
+}
+
+fn export_matrix(args: &[String]) -> CommandResult {
+    let (file, output) = match args {
+        [file] => (file, csv::matrix_path(Path::new(file))),
+        [file, output] => (file, PathBuf::from(output)),
+        _ => return Err(usage("export-matrix <arquivo> [saída]")),
+    };
+
+    let graph = load_graph(file)?;
+
+    write_file(&output, &csv::to_adjacency_matrix(&graph))?;
+
+    Ok(true)
+}
+
+fn parse_dot_format(format: &str) -> Result<DotFormat, String> {
+    match format {
+        "png" => Ok(DotFormat::Png),
+        "svg" => Ok(DotFormat::Svg),
+        "pdf" => Ok(DotFormat::Pdf),
+        _ => Err(format!("Formato inválido: {format}, use png, svg ou pdf")),
+    }
+}
+
+/// Cheapest path in weighted graphs, the one with fewest edges otherwise
+fn highlighted_path(graph: &Graph, from: &str, to: &str) -> Result<Graph, String> {
+    let from = find_node(graph, from)?;
+    let to = find_node(graph, to)?;
+
+    let path = if graph.is_weighted() {
+        graph.calculate_path(from, to).map(|(_, path)| path)
+    } else {
+        graph.get_path(from, to, PathMode::Breadth)
+    };
+
+    path.map(|path| graph.path_subgraph(&path))
+        .map_err(|err| err.to_string())
+}
+
+fn add_edge(args: &[String]) -> CommandResult {
+    let [file, from, to, weight @ ..] = args else {
+        return Err(usage("add-edge <arquivo> <origem> <destino> [peso]"));
+    };
+
+    if !graph_exporter::can_write(Path::new(file)) {
+        return Err(format!(
+            "O formato do arquivo {file} não pode ser salvo, converta-o com o comando convert"
+        ));
+    }
+
+    let mut graph = load_graph(file)?;
+    let from = find_node(&graph, from)?.code;
+    let to = find_node(&graph, to)?.code;
+
+    let weight = match (weight, graph.is_weighted()) {
+        ([weight], true) => match weight.parse::<Weight>() {
+            Ok(weight) if !weight.is_zero() => weight,
+            _ => {
+                return Err(format!(
+                    "Peso inválido: {weight}, ele deve ser um número diferente de 0"
+                ))
+            }
+        },
+        ([], false) => Weight::ONE,
+        ([], true) => return Err("O grafo é ponderado, informe o peso da aresta".to_string()),
+        _ => return Err("O grafo não é ponderado, a aresta não pode ter peso".to_string()),
+    };
+
+    graph
+        .add_edge(Edge {
+            from,
+            to,
+            weight,
+            attributes: Attributes::new(),
+        })
+        .map_err(|err| err.to_string())?;
+
+    match graph_exporter::write_graph(&graph, Path::new(file)) {
+        Ok(_) => Ok(true),
+        Err(err) => Err(format!("Erro ao salvar o grafo em {file}: {err}")),
+    }
+}
+
+fn convert(args: &[String]) -> CommandResult {
+    let [file, output] = args else {
+        return Err(usage("convert <arquivo> <saída>"));
+    };
+
+    let graph = load_graph(file)?;
+
+    write_graph(&graph, output)
+}
+
+fn usage(command: &str) -> String {
+    format!("Uso: n1_project {command}")
+}
+
+fn read_file(file: &str) -> Result<String, String> {
+    fs::read_to_string(file).map_err(|err| format!("Erro ao ler o arquivo {file}: {err}"))
+}
+
+/// Writes and prints the path, so scripts can use the output
+fn write_file(path: &Path, contents: &str) -> Result<(), String> {
+    fs::write(path, contents)
+        .map_err(|err| format!("Erro ao salvar o arquivo {}: {err}", path.display()))?;
+
+    println!("{}", path.display());
+
+    Ok(())
+}
+
+fn write_graph(graph: &Graph, output: &str) -> CommandResult {
+    match graph_exporter::write_graph(graph, Path::new(output)) {
+        Ok(_) => {
+            println!("{output}");
+            Ok(true)
+        }
+        Err(err) => Err(format!("Erro ao salvar o grafo em {output}: {err}")),
+    }
+}
+
+fn load_graph(file: &str) -> Result<Graph, String> {
+    graph_importer::import_graph(Path::new(file))
+        .map_err(|err| format!("Erro ao ler o grafo do arquivo {file}: {err}"))
+}
+
+fn find_node<'a>(graph: &'a Graph, code: &str) -> Result<&'a Node, String> {
+    let code = code
+        .parse()
+        .map_err(|_| format!("Código inválido: {code}"))?;
+
+    graph
+        .find_by_code(code)
+        .ok_or(GraphError::NodeNotFound(code).to_string())
+}
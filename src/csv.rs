@@ -0,0 +1,580 @@
+use std::path::{Path, PathBuf};
+
+use crate::{
+    error::GraphError,
+    graph::{Edge, Graph},
+    graph_importer,
+    node::{Attributes, Node},
+    weight::Weight,
+};
+
+/// Paths of the nodes and edges lists exported for the graph saved in
+/// `graph_file`, e.g. `campus.json` is exported as `campus.nodes.csv` and
+/// `campus.edges.csv`
+pub fn list_paths(graph_file: &Path) -> (PathBuf, PathBuf) {
+    (
+        graph_file.with_extension("nodes.csv"),
+        graph_file.with_extension("edges.csv"),
+    )
+}
+
+/// Path of the adjacency matrix exported for the graph saved in `graph_file`,
+/// e.g. `campus.json` is exported as `campus.matrix.csv`
+pub fn matrix_path(graph_file: &Path) -> PathBuf {
+    graph_file.with_extension("matrix.csv")
+}
+
+/// `code,name,local_type` with a header row
+pub fn nodes_to_csv(graph: &Graph) -> String {
+    let mut f = String::from("code,name,local_type\n");
+
+    for node in graph.nodes() {
+        f.push_str(&format!(
+            "{},{},{}\n",
+            node.code,
+            escape(&node.name),
+            escape(&node.local_type)
+        ));
+    }
+
+    f
+}
+
+/// `from,to,weight` with a header row, the weight column is only written for
+/// weighted graphs
+pub fn edges_to_csv(graph: &Graph) -> String {
+    let mut f = String::from(if graph.is_weighted() {
+        "from,to,weight\n"
+    } else {
+        "from,to\n"
+    });
+
+    for edge in graph.edges() {
+        if graph.is_weighted() {
+            f.push_str(&format!("{},{},{}\n", edge.from, edge.to, edge.weight));
+        } else {
+            f.push_str(&format!("{},{}\n", edge.from, edge.to));
+        }
+    }
+
+    f
+}
+
+/// Weighted adjacency matrix, the first row and column have the node codes
+/// and 0 means there is no edge. Unweighted edges are written as 1
+pub fn to_adjacency_matrix(graph: &Graph) -> String {
+    let codes: Vec<usize> = graph.nodes().map(|node| node.code).collect();
+
+    let mut f = String::new();
+
+    for code in &codes {
+        f.push_str(&format!(",{code}"));
+    }
+
+    f.push('\n');
+
+    for &from in &codes {
+        f.push_str(&from.to_string());
+
+        for &to in &codes {
+            let weight = graph
+                .find_edge_by_from_to(from, to)
+                .map_or(Weight::ZERO, |edge| {
+                    if graph.is_weighted() {
+                        edge.weight
+                    } else {
+                        Weight::ONE
+                    }
+                });
+
+            f.push_str(&format!(",{weight}"));
+        }
+
+        f.push('\n');
+    }
+
+    f
+}
+
+/// Builds a graph from a nodes CSV and an optional edges CSV.
+///
+/// The columns can be named in a header row (`code,name,local_type` and
+/// `from,to,weight`), in any order, otherwise they are read in that order.
+/// The graph is weighted when the edges have a weight column
+pub fn from_csv(nodes: &str, edges: Option<&str>, is_directed: bool) -> Result<Graph, GraphError> {
+    let nodes = Table::parse(nodes, &["code", "name", "local_type"])?;
+    let edges = match edges {
+        Some(edges) => Some(Table::parse(edges, &["from", "to", "weight"])?),
+        None => None,
+    };
+
+    let is_weighted = edges
+        .as_ref()
+        .is_some_and(|edges| edges.has_column("weight"));
+
+    let mut graph = Graph::new(is_weighted, is_directed);
+
+    for row in &nodes.rows {
+        let node = Node {
+            code: row.code("code")?,
+            name: row.required("name")?.to_string(),
+            local_type: row.get("local_type").unwrap_or_default().to_string(),
+            attributes: Attributes::new(),
+        };
+
+        graph
+            .add_node(node)
+            .map_err(|err| row.error(&err.to_string()))?;
+    }
+
+    for row in edges.iter().flat_map(|edges| &edges.rows) {
+        let weight = match row.get("weight") {
+            Some(weight) if is_weighted => {
+                graph_importer::parse_weight(weight).ok_or_else(|| {
+                    row.error(&format!(
+                        "o peso da aresta precisa ser um número diferente de 0: {weight}"
+                    ))
+                })?
+            }
+            _ => Weight::ONE,
+        };
+
+        let edge = Edge {
+            from: row.code("from")?,
+            to: row.code("to")?,
+            weight,
+            attributes: Attributes::new(),
+        };
+
+        graph
+            .add_edge(edge)
+            .map_err(|err| row.error(&err.to_string()))?;
+    }
+
+    Ok(graph)
+}
+
+/// Builds a graph from a weighted adjacency matrix like the one written by
+/// `to_adjacency_matrix`, names and types come from the optional nodes CSV,
+/// otherwise the code is used as the name.
+///
+/// A matrix can't tell a directed graph with edges both ways, or a weighted
+/// one with weights all 1, apart from the others, so that's informed.
+/// Undirected matrices must be symmetric and unweighted ones only have 0 or 1
+pub fn from_adjacency_matrix(
+    matrix: &str,
+    nodes: Option<&str>,
+    is_directed: bool,
+    is_weighted: bool,
+) -> Result<Graph, GraphError> {
+    let rows = parse_rows(matrix)?;
+
+    let Some((header, rows)) = rows.split_first() else {
+        return Err(parse_error(1, 1, "a matriz está vazia"));
+    };
+
+    let mut codes = Vec::new();
+
+    for field in header.fields.iter().skip(1) {
+        codes.push(parse_code(field, header.line)?);
+    }
+
+    if rows.len() != codes.len() {
+        return Err(parse_error(
+            header.line,
+            1,
+            &format!(
+                "a matriz tem {} colunas e {} linhas de vértices",
+                codes.len(),
+                rows.len()
+            ),
+        ));
+    }
+
+    let mut weights = vec![vec![None; codes.len()]; codes.len()];
+    // Line and column of each value, for the symmetry errors
+    let mut positions = vec![vec![(0, 0); codes.len()]; codes.len()];
+
+    for (i, row) in rows.iter().enumerate() {
+        let Some((label, values)) = row.fields.split_first() else {
+            continue;
+        };
+
+        if parse_code(label, row.line)? != codes[i] {
+            return Err(parse_error(
+                row.line,
+                label.column,
+                &format!("esperado o vértice {} nessa linha", codes[i]),
+            ));
+        }
+
+        if values.len() != codes.len() {
+            return Err(parse_error(
+                row.line,
+                1,
+                &format!("esperados {} valores na linha", codes.len()),
+            ));
+        }
+
+        for (j, value) in values.iter().enumerate() {
+            positions[i][j] = (row.line, value.column);
+            weights[i][j] = match value.text.trim() {
+                "" | "0" => None,
+                "1" => Some(Weight::ONE),
+                text if !is_weighted => {
+                    return Err(parse_error(
+                        row.line,
+                        value.column,
+                        &format!("o grafo não é ponderado, use 1 quando há aresta: {text}"),
+                    ))
+                }
+                text => graph_importer::parse_weight(text)
+                    .map(Some)
+                    .ok_or_else(|| {
+                        parse_error(
+                            row.line,
+                            value.column,
+                            &format!("peso inválido: {text}, use 0 quando não há aresta"),
+                        )
+                    })?,
+            };
+        }
+    }
+
+    if !is_directed {
+        for i in 0..codes.len() {
+            for j in 0..i {
+                if weights[i][j] != weights[j][i] {
+                    let (line, column) = positions[i][j];
+
+                    return Err(parse_error(
+                        line,
+                        column,
+                        &format!(
+                            "a matriz não é simétrica entre {} e {}, o grafo precisa ser direcionado",
+                            codes[i], codes[j]
+                        ),
+                    ));
+                }
+            }
+        }
+    }
+
+    let named_nodes = match nodes {
+        Some(nodes) => from_csv(nodes, None, is_directed)?,
+        None => Graph::new(is_weighted, is_directed),
+    };
+
+    let mut graph = Graph::new(is_weighted, is_directed);
+
+    for &code in &codes {
+        let node = named_nodes
+            .find_by_code(code)
+            .cloned()
+            .unwrap_or_else(|| Node {
+                code,
+                name: code.to_string(),
+                local_type: String::new(),
+                attributes: Attributes::new(),
+            });
+
+        graph.add_node(node)?;
+    }
+
+    for (i, &from) in codes.iter().enumerate() {
+        // Undirected edges appear twice, only the upper half is read
+        let start = if is_directed { 0 } else { i };
+
+        for (j, &to) in codes.iter().enumerate().skip(start) {
+            if let Some(weight) = weights[i][j] {
+                graph.add_edge(Edge {
+                    from,
+                    to,
+                    weight,
+                    attributes: Attributes::new(),
+                })?;
+            }
+        }
+    }
+
+    Ok(graph)
+}
+
+fn escape(text: &str) -> String {
+    if text.contains([',', '"', '\n']) {
+        format!("\"{}\"", text.replace('"', "\"\""))
+    } else {
+        text.to_string()
+    }
+}
+
+struct Field {
+    text: String,
+    column: usize,
+}
+
+struct Row {
+    fields: Vec<Field>,
+    line: usize,
+}
+
+/// Rows with the fields in the order given to `Table::parse`
+struct Table {
+    columns: Vec<&'static str>,
+    rows: Vec<TableRow>,
+}
+
+struct TableRow {
+    values: Vec<(&'static str, Field)>,
+    line: usize,
+}
+
+impl Table {
+    /// Uses the first row as header when its first field isn't a number,
+    /// otherwise the fields are read in the order of `columns`
+    fn parse(source: &str, columns: &[&'static str]) -> Result<Table, GraphError> {
+        let rows = parse_rows(source)?;
+        // Without a header, the columns that no row has are left out
+        let width = rows.iter().map(|row| row.fields.len()).max().unwrap_or(0);
+        let mut rows = rows.into_iter().peekable();
+
+        let has_header = rows.peek().is_some_and(|row| {
+            row.fields
+                .first()
+                .is_some_and(|field| field.text.trim().parse::<f64>().is_err())
+        });
+
+        let order: Vec<Option<&'static str>> = match rows.next_if(|_| has_header) {
+            Some(header) => header
+                .fields
+                .iter()
+                .map(|field| {
+                    let name = field.text.trim().to_lowercase();
+                    columns.iter().copied().find(|&column| column == name)
+                })
+                .collect(),
+            None => columns.iter().copied().take(width).map(Some).collect(),
+        };
+
+        let rows = rows
+            .map(|row| TableRow {
+                values: order
+                    .iter()
+                    .zip(row.fields)
+                    .filter_map(|(column, field)| Some(((*column)?, field)))
+                    .collect(),
+                line: row.line,
+            })
+            .collect();
+
+        Ok(Table {
+            columns: order.into_iter().flatten().collect(),
+            rows,
+        })
+    }
+
+    fn has_column(&self, column: &str) -> bool {
+        self.columns.contains(&column)
+    }
+}
+
+impl TableRow {
+    fn field(&self, column: &str) -> Option<&Field> {
+        self.values
+            .iter()
+            .find(|(name, _)| *name == column)
+            .map(|(_, field)| field)
+    }
+
+    fn get(&self, column: &str) -> Option<&str> {
+        self.field(column).map(|field| field.text.trim())
+    }
+
+    fn required(&self, column: &str) -> Result<&str, GraphError> {
+        self.get(column)
+            .ok_or_else(|| self.error(&format!("a coluna {column} está faltando")))
+    }
+
+    fn code(&self, column: &str) -> Result<usize, GraphError> {
+        match self.field(column) {
+            Some(field) => parse_code(field, self.line),
+            None => Err(self.error(&format!("a coluna {column} está faltando"))),
+        }
+    }
+
+    fn error(&self, message: &str) -> GraphError {
+        parse_error(self.line, 1, message)
+    }
+}
+
+fn parse_code(field: &Field, line: usize) -> Result<usize, GraphError> {
+    match field.text.trim().parse() {
+        Ok(code) if code > 0 => Ok(code),
+        _ => Err(parse_error(
+            line,
+            field.column,
+            &format!("código inválido: {}", field.text.trim()),
+        )),
+    }
+}
+
+/// Splits the comma separated rows, fields can be quoted to have commas,
+/// quotes (`""`) and line breaks. Empty lines are skipped
+fn parse_rows(source: &str) -> Result<Vec<Row>, GraphError> {
+    let mut rows = Vec::new();
+    let mut chars = source.trim_start_matches('\u{feff}').chars().peekable();
+    let (mut line, mut column) = (1, 1);
+
+    while chars.peek().is_some() {
+        let mut row = Row {
+            fields: Vec::new(),
+            line,
+        };
+
+        loop {
+            let mut field = Field {
+                text: String::new(),
+                column,
+            };
+
+            if chars.peek() == Some(&'"') {
+                chars.next();
+                column += 1;
+
+                loop {
+                    match chars.next() {
+                        Some('"') if chars.peek() == Some(&'"') => {
+                            chars.next();
+                            column += 2;
+                            field.text.push('"');
+                        }
+                        Some('"') => {
+                            column += 1;
+                            break;
+                        }
+                        Some('\n') => {
+                            line += 1;
+                            column = 1;
+                            field.text.push('\n');
+                        }
+                        Some(c) => {
+                            column += 1;
+                            field.text.push(c);
+                        }
+                        None => return Err(parse_error(line, field.column, "aspas não fechadas")),
+                    }
+                }
+            }
+
+            while let Some(&c) = chars.peek() {
+                if c == ',' || c == '\n' {
+                    break;
+                }
+
+                chars.next();
+                column += 1;
+
+                if c != '\r' {
+                    field.text.push(c);
+                }
+            }
+
+            row.fields.push(field);
+
+            match chars.next() {
+                Some(',') => column += 1,
+                _ => {
+                    line += 1;
+                    column = 1;
+                    break;
+                }
+            }
+        }
+
+        let is_empty = row.fields.len() == 1 && row.fields[0].text.trim().is_empty();
+
+        if !is_empty {
+            rows.push(row);
+        }
+    }
+
+    Ok(rows)
+}
+
+fn parse_error(line: usize, column: usize, message: &str) -> GraphError {
+    GraphError::Parse {
+        line,
+        column,
+        message: message.to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::fixtures::campus;
+
+    /// The campus without attributes, which the CSV files don't have
+    fn plain_campus(is_directed: bool) -> Graph {
+        let mut graph = campus(is_directed);
+        graph.remove_node_attribute(1, "andar").unwrap();
+        graph.remove_edge_attribute(1, 2, "coberta").unwrap();
+
+        graph
+    }
+
+    #[test]
+    fn round_trips_the_lists() {
+        for is_directed in [false, true] {
+            let graph = plain_campus(is_directed);
+            let edges = edges_to_csv(&graph);
+            let parsed = from_csv(&nodes_to_csv(&graph), Some(&edges), is_directed).unwrap();
+
+            assert_eq!(parsed.to_json(), graph.to_json());
+        }
+    }
+
+    #[test]
+    fn reads_headers_in_any_order_or_none() {
+        let nodes = "name,code\n\"Sala \"\"A\"\", 1\",3\nPátio,4\n";
+        let graph = from_csv(nodes, Some("to,from\n4,3\n"), true).unwrap();
+
+        assert_eq!(graph.find_by_code(3).unwrap().name, "Sala \"A\", 1");
+        assert!(graph.find_edge_by_from_to(3, 4).is_some());
+        assert!(!graph.is_weighted());
+
+        let graph = from_csv("1,A,sala\n2,B,\n", Some("1,2,0.5\n"), false).unwrap();
+
+        assert_eq!(graph.find_by_code(1).unwrap().local_type, "sala");
+        assert_eq!(
+            graph.find_edge_by_from_to(2, 1).unwrap().weight,
+            Weight::Float(0.5)
+        );
+        assert!(from_csv("1,A\n", Some("1,2\n"), false).is_err());
+    }
+
+    #[test]
+    fn round_trips_the_matrix() {
+        for is_directed in [false, true] {
+            let graph = plain_campus(is_directed);
+            let nodes = nodes_to_csv(&graph);
+            let matrix = to_adjacency_matrix(&graph);
+            let parsed = from_adjacency_matrix(&matrix, Some(&nodes), is_directed, true).unwrap();
+
+            assert_eq!(parsed.to_json(), graph.to_json());
+        }
+    }
+
+    #[test]
+    fn rejects_matrices_that_contradict_the_flags() {
+        let matrix = ",1,2\n1,0,2\n2,0,0\n";
+
+        assert!(from_adjacency_matrix(matrix, None, false, true).is_err());
+        assert!(from_adjacency_matrix(matrix, None, true, false).is_err());
+
+        let graph = from_adjacency_matrix(",1,2\n1,0,1\n2,1,0\n", None, false, true).unwrap();
+
+        assert!(graph.is_weighted() && !graph.is_directed());
+        assert_eq!(graph.edges().count(), 1);
+    }
+}
@@ -0,0 +1,566 @@
+use std::{collections::HashMap, iter::Peekable, str::Chars};
+
+use crate::{
+    error::GraphError,
+    graph::{Edge, Graph},
+    graph_importer,
+    node::{Attributes, Node},
+    weight::Weight,
+};
+
+#[derive(Debug, Clone, PartialEq)]
+enum TokenKind {
+    /// Identifier, number or quoted string
+    Id(String),
+    LBrace,
+    RBrace,
+    LBracket,
+    RBracket,
+    Equals,
+    Semicolon,
+    Comma,
+    /// `->` when true, `--` otherwise
+    EdgeOp(bool),
+}
+
+#[derive(Debug)]
+struct Token {
+    kind: TokenKind,
+    line: usize,
+    column: usize,
+}
+
+struct ParsedNode {
+    id: String,
+    attributes: HashMap<String, String>,
+}
+
+struct ParsedEdge {
+    from: String,
+    to: String,
+    attributes: HashMap<String, String>,
+    line: usize,
+    column: usize,
+}
+
+/// Builds a graph from the DOT subset written by `graph_exporter`: node
+/// statements with `label` (and optionally `local_type`) and edge statements
+/// with `label` as the weight.
+///
+/// Numeric node ids are used as codes, other ids get the next free code and
+/// are used as the name when the node has no label. The graph is weighted
+/// when any edge has a label, edges without one get weight 1
+pub fn parse_dot(source: &str) -> Result<Graph, GraphError> {
+    let tokens = tokenize(source)?;
+    let mut parser = Parser {
+        tokens: &tokens,
+        position: 0,
+        end: end_position(source),
+        nodes: Vec::new(),
+        edges: Vec::new(),
+    };
+
+    let is_directed = parser.parse_header()?;
+    parser.parse_statements(is_directed)?;
+
+    build_graph(parser.nodes, parser.edges, is_directed)
+}
+
+struct Parser<'a> {
+    tokens: &'a [Token],
+    position: usize,
+    /// Line and column after the last character, for errors at the end of the file
+    end: (usize, usize),
+    nodes: Vec<ParsedNode>,
+    edges: Vec<ParsedEdge>,
+}
+
+impl Parser<'_> {
+    fn peek(&self) -> Option<&TokenKind> {
+        self.tokens.get(self.position).map(|token| &token.kind)
+    }
+
+    fn next(&mut self) -> Result<&Token, GraphError> {
+        let token = self
+            .tokens
+            .get(self.position)
+            .ok_or_else(|| parse_error(self.end, "fim inesperado do arquivo"))?;
+        self.position += 1;
+
+        Ok(token)
+    }
+
+    fn expect(&mut self, kind: TokenKind, description: &str) -> Result<(), GraphError> {
+        let token = self.next()?;
+
+        if token.kind != kind {
+            return Err(parse_error(
+                (token.line, token.column),
+                &format!("esperado {description}"),
+            ));
+        }
+
+        Ok(())
+    }
+
+    fn expect_id(&mut self) -> Result<String, GraphError> {
+        let token = self.next()?;
+
+        match &token.kind {
+            TokenKind::Id(id) => Ok(id.clone()),
+            _ => Err(parse_error(
+                (token.line, token.column),
+                "esperado um identificador",
+            )),
+        }
+    }
+
+    /// `[strict] (graph | digraph) [id] {`, returns whether the graph is directed
+    fn parse_header(&mut self) -> Result<bool, GraphError> {
+        let mut token = self.next()?;
+
+        if token.kind == TokenKind::Id("strict".to_string()) {
+            token = self.next()?;
+        }
+
+        let is_directed = match &token.kind {
+            TokenKind::Id(keyword) if keyword == "graph" => false,
+            TokenKind::Id(keyword) if keyword == "digraph" => true,
+            _ => {
+                return Err(parse_error(
+                    (token.line, token.column),
+                    "esperado graph ou digraph",
+                ))
+            }
+        };
+
+        if let Some(TokenKind::Id(_)) = self.peek() {
+            self.position += 1;
+        }
+
+        self.expect(TokenKind::LBrace, "{")?;
+
+        Ok(is_directed)
+    }
+
+    fn parse_statements(&mut self, is_directed: bool) -> Result<(), GraphError> {
+        loop {
+            let token = self.next()?;
+            let (line, column) = (token.line, token.column);
+
+            let id = match &token.kind {
+                TokenKind::RBrace => break,
+                TokenKind::Semicolon => continue,
+                TokenKind::Id(id) => id.clone(),
+                TokenKind::LBrace => {
+                    return Err(parse_error((line, column), "subgrafos não são suportados"))
+                }
+                _ => return Err(parse_error((line, column), "declaração inválida")),
+            };
+
+            match self.peek() {
+                // Default attributes, like `node [style=filled]`, don't change the model
+                Some(TokenKind::LBracket) if ["graph", "node", "edge"].contains(&id.as_str()) => {
+                    self.parse_attributes()?;
+                }
+                // Graph attribute, like `rankdir=LR`
+                Some(TokenKind::Equals) => {
+                    self.position += 1;
+                    self.expect_id()?;
+                }
+                Some(TokenKind::EdgeOp(_)) => self.parse_edges(id, is_directed, line, column)?,
+                _ => {
+                    let attributes = if self.peek() == Some(&TokenKind::LBracket) {
+                        self.parse_attributes()?
+                    } else {
+                        HashMap::new()
+                    };
+
+                    self.nodes.push(ParsedNode { id, attributes });
+                }
+            }
+        }
+
+        match self.tokens.get(self.position) {
+            Some(token) => Err(parse_error(
+                (token.line, token.column),
+                "conteúdo após o fim do grafo",
+            )),
+            None => Ok(()),
+        }
+    }
+
+    /// Edge chain like `1 -- 2 -- 3 [label="4"]`, the attributes apply to every edge
+    fn parse_edges(
+        &mut self,
+        from: String,
+        is_directed: bool,
+        line: usize,
+        column: usize,
+    ) -> Result<(), GraphError> {
+        let mut ids = vec![from];
+
+        while let Some(&TokenKind::EdgeOp(directed)) = self.peek() {
+            if directed != is_directed {
+                let token = &self.tokens[self.position];
+                let message = if is_directed {
+                    "grafos direcionados usam ->"
+                } else {
+                    "grafos não direcionados usam --"
+                };
+
+                return Err(parse_error((token.line, token.column), message));
+            }
+
+            self.position += 1;
+            ids.push(self.expect_id()?);
+        }
+
+        let attributes = if self.peek() == Some(&TokenKind::LBracket) {
+            self.parse_attributes()?
+        } else {
+            HashMap::new()
+        };
+
+        for pair in ids.windows(2) {
+            self.edges.push(ParsedEdge {
+                from: pair[0].clone(),
+                to: pair[1].clone(),
+                attributes: attributes.clone(),
+                line,
+                column,
+            });
+        }
+
+        Ok(())
+    }
+
+    /// `[key=value, key=value; ...]`, repeated lists are merged
+    fn parse_attributes(&mut self) -> Result<HashMap<String, String>, GraphError> {
+        let mut attributes = HashMap::new();
+
+        while self.peek() == Some(&TokenKind::LBracket) {
+            self.position += 1;
+
+            loop {
+                match self.peek() {
+                    Some(TokenKind::RBracket) => {
+                        self.position += 1;
+                        break;
+                    }
+                    Some(TokenKind::Comma | TokenKind::Semicolon) => self.position += 1,
+                    _ => {
+                        let key = self.expect_id()?;
+                        self.expect(TokenKind::Equals, "=")?;
+                        let value = self.expect_id()?;
+
+                        attributes.insert(key, value);
+                    }
+                }
+            }
+        }
+
+        Ok(attributes)
+    }
+}
+
+fn build_graph(
+    nodes: Vec<ParsedNode>,
+    edges: Vec<ParsedEdge>,
+    is_directed: bool,
+) -> Result<Graph, GraphError> {
+    let is_weighted = edges
+        .iter()
+        .any(|edge| edge.attributes.contains_key("label"));
+
+    // A node can be declared more than once, or only appear in edges
+    let mut attributes: Vec<(String, HashMap<String, String>)> = Vec::new();
+
+    let edge_ids = edges
+        .iter()
+        .flat_map(|edge| [edge.from.clone(), edge.to.clone()]);
+    let declared = nodes.into_iter().map(|node| (node.id, node.attributes));
+
+    for (id, node_attributes) in declared.chain(edge_ids.map(|id| (id, HashMap::new()))) {
+        match attributes.iter_mut().find(|(known, _)| *known == id) {
+            Some((_, known_attributes)) => known_attributes.extend(node_attributes),
+            None => attributes.push((id, node_attributes)),
+        }
+    }
+
+    let codes = graph_importer::assign_codes(attributes.iter().map(|(id, _)| id.as_str()));
+
+    let mut graph = Graph::new(is_weighted, is_directed);
+
+    for (id, mut node_attributes) in attributes {
+        let code = codes[&id];
+
+        graph.add_node(Node {
+            code,
+            name: node_attributes.remove("label").unwrap_or(id),
+            local_type: node_attributes.remove("local_type").unwrap_or_default(),
+            attributes: Attributes::new(),
+        })?;
+    }
+
+    for edge in edges {
+        let weight = match edge.attributes.get("label") {
+            Some(label) => match graph_importer::parse_weight(label) {
+                Some(weight) => weight,
+                None => {
+                    return Err(parse_error(
+                        (edge.line, edge.column),
+                        &format!("o peso da aresta precisa ser um número diferente de 0: {label}"),
+                    ))
+                }
+            },
+            None => Weight::ONE,
+        };
+
+        graph.add_edge(Edge {
+            from: codes[&edge.from],
+            to: codes[&edge.to],
+            weight,
+            attributes: Attributes::new(),
+        })?;
+    }
+
+    Ok(graph)
+}
+
+struct Lexer<'a> {
+    chars: Peekable<Chars<'a>>,
+    line: usize,
+    column: usize,
+}
+
+impl Lexer<'_> {
+    fn peek(&mut self) -> Option<char> {
+        self.chars.peek().copied()
+    }
+
+    fn advance(&mut self) -> Option<char> {
+        let c = self.chars.next();
+
+        if c == Some('\n') {
+            self.line += 1;
+            self.column = 1;
+        } else {
+            self.column += 1;
+        }
+
+        c
+    }
+
+    fn skip_line(&mut self) {
+        while self.peek().is_some_and(|c| c != '\n') {
+            self.advance();
+        }
+    }
+
+    fn take_while(&mut self, mut id: String, predicate: impl Fn(char) -> bool) -> String {
+        while self.peek().is_some_and(&predicate) {
+            id.push(self.advance().unwrap());
+        }
+
+        id
+    }
+}
+
+fn tokenize(source: &str) -> Result<Vec<Token>, GraphError> {
+    let mut tokens = Vec::new();
+    let mut lexer = Lexer {
+        chars: source.chars().peekable(),
+        line: 1,
+        column: 1,
+    };
+
+    while let Some(c) = lexer.peek() {
+        let start = (lexer.line, lexer.column);
+
+        if c.is_whitespace() {
+            lexer.advance();
+            continue;
+        }
+
+        // Preprocessor style line, like `# 1 "file"`
+        if c == '#' && start.1 == 1 {
+            lexer.skip_line();
+            continue;
+        }
+
+        lexer.advance();
+
+        let kind = match c {
+            '/' => {
+                match lexer.advance() {
+                    Some('/') => lexer.skip_line(),
+                    Some('*') => loop {
+                        match lexer.advance() {
+                            Some('*') if lexer.peek() == Some('/') => {
+                                lexer.advance();
+                                break;
+                            }
+                            Some(_) => continue,
+                            None => return Err(parse_error(start, "comentário não fechado")),
+                        }
+                    },
+                    _ => return Err(parse_error(start, "caractere inesperado: /")),
+                }
+                continue;
+            }
+            '{' => TokenKind::LBrace,
+            '}' => TokenKind::RBrace,
+            '[' => TokenKind::LBracket,
+            ']' => TokenKind::RBracket,
+            '=' => TokenKind::Equals,
+            ';' => TokenKind::Semicolon,
+            ',' => TokenKind::Comma,
+            '-' => match lexer.peek() {
+                Some('-') => {
+                    lexer.advance();
+                    TokenKind::EdgeOp(false)
+                }
+                Some('>') => {
+                    lexer.advance();
+                    TokenKind::EdgeOp(true)
+                }
+                // Negative number
+                _ => TokenKind::Id(
+                    lexer.take_while("-".to_string(), |c| c.is_ascii_digit() || c == '.'),
+                ),
+            },
+            '"' => {
+                let mut id = String::new();
+
+                loop {
+                    match lexer.advance() {
+                        Some('"') => break,
+                        Some('\\') if lexer.peek() == Some('"') => {
+                            id.push(lexer.advance().unwrap());
+                        }
+                        Some(c) => id.push(c),
+                        None => return Err(parse_error(start, "texto entre aspas não fechado")),
+                    }
+                }
+
+                TokenKind::Id(id)
+            }
+            c if is_id_char(c) => TokenKind::Id(lexer.take_while(c.to_string(), is_id_char)),
+            '<' => return Err(parse_error(start, "rótulos HTML não são suportados")),
+            c => return Err(parse_error(start, &format!("caractere inesperado: {c}"))),
+        };
+
+        tokens.push(Token {
+            kind,
+            line: start.0,
+            column: start.1,
+        });
+    }
+
+    Ok(tokens)
+}
+
+fn is_id_char(c: char) -> bool {
+    c.is_alphanumeric() || c == '_' || c == '.'
+}
+
+fn end_position(source: &str) -> (usize, usize) {
+    let line = source.lines().count().max(1);
+    let column = source.lines().last().map_or(0, |last| last.chars().count()) + 1;
+
+    (line, column)
+}
+
+fn parse_error((line, column): (usize, usize), message: &str) -> GraphError {
+    GraphError::Parse {
+        line,
+        column,
+        message: message.to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{fixtures::campus, graph_exporter};
+
+    #[test]
+    fn reads_back_the_exported_dot() {
+        let graph = campus(false);
+        let parsed = parse_dot(&graph_exporter::to_dot(&graph, None, Default::default())).unwrap();
+
+        // DOT only keeps the codes, names and weights
+        let names = |graph: &Graph| -> Vec<(usize, String)> {
+            graph
+                .nodes()
+                .map(|node| (node.code, node.name.clone()))
+                .collect()
+        };
+        let edges = |graph: &Graph| -> Vec<(usize, usize, Weight)> {
+            graph
+                .edges()
+                .map(|edge| (edge.from, edge.to, edge.weight))
+                .collect()
+        };
+
+        assert!(!parsed.is_directed() && parsed.is_weighted());
+        assert_eq!(names(&parsed), names(&graph));
+        assert_eq!(edges(&parsed), edges(&graph));
+    }
+
+    #[test]
+    fn reads_quoted_ids_chains_and_comments() {
+        let source = r#"# written by hand
+            strict digraph campus {
+                node [shape=box]; // default attributes are ignored
+                3 [label="Entrada", local_type="portão"]
+                "Sala 1" -> biblioteca -> 3 [label="2"]
+                /* a node declared again keeps its label */
+                3 [color=red]
+            }
+        "#;
+
+        let graph = parse_dot(source).unwrap();
+        let nodes: Vec<(usize, &str, &str)> = graph
+            .nodes()
+            .map(|node| (node.code, node.name.as_str(), node.local_type.as_str()))
+            .collect();
+        let edges: Vec<(usize, usize)> = graph.edges().map(|edge| (edge.from, edge.to)).collect();
+
+        assert!(graph.is_directed() && graph.is_weighted());
+        assert_eq!(
+            nodes,
+            vec![
+                (3, "Entrada", "portão"),
+                (4, "Sala 1", ""),
+                (5, "biblioteca", "")
+            ]
+        );
+        assert_eq!(edges, vec![(4, 5), (5, 3)]);
+        assert_eq!(
+            graph.find_edge_by_from_to(5, 3).unwrap().weight,
+            Weight::Integer(2)
+        );
+    }
+
+    #[test]
+    fn reports_where_the_error_is() {
+        let Err(GraphError::Parse { line, column, .. }) =
+            parse_dot("graph {\n  1 -- 2 [label=0]\n}")
+        else {
+            panic!("a weight of 0 is invalid");
+        };
+
+        assert_eq!((line, column), (2, 3));
+        assert!(parse_dot("graph { subgraph { 1 } }").is_err());
+    }
+
+    #[test]
+    fn ids_with_the_same_number_get_different_codes() {
+        let graph = parse_dot(r#"graph { 7 -- "07" }"#).unwrap();
+
+        assert_eq!(graph.find_by_code(8).unwrap().name, "07");
+        assert!(graph.find_edge_by_from_to(7, 8).is_some());
+    }
+}
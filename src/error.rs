@@ -0,0 +1,181 @@
+use std::{error::Error, fmt::Display, io};
+
+#[derive(Debug)]
+pub enum GraphError {
+    NodeNotFound(usize),
+    NodeAlreadyExists(usize),
+    EdgeNotFound {
+        from: usize,
+        to: usize,
+    },
+    EdgeAlreadyExists {
+        from: usize,
+        to: usize,
+    },
+    NoPath {
+        from: usize,
+        to: usize,
+    },
+    /// `position` is the index, in the informed codes, where the cycle breaks
+    InvalidCycle {
+        position: usize,
+        kind: CycleErrorKind,
+    },
+    /// The operation is only defined for undirected graphs
+    DirectedGraph,
+    Parse {
+        line: usize,
+        column: usize,
+        message: String,
+    },
+    Io(io::Error),
+    /// The file extension isn't a format the graph can be saved in
+    UnsupportedFormat(String),
+    /// The file was saved by a newer version of the program
+    UnsupportedVersion(u64),
+    /// Weights can only be scaled by a finite factor greater than 0
+    InvalidScaleFactor(f64),
+    /// Scaling the weight of the edge overflows or makes it 0
+    ScaledWeightOutOfRange {
+        from: usize,
+        to: usize,
+    },
+    /// The weight isn't an integer or a finite number, or it's 0
+    InvalidWeight(String),
+    /// Codes of a cycle with negative total weight, first and last are equal.
+    /// Shortest paths aren't defined when one can be reached
+    NegativeCycle(Vec<usize>),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum CycleErrorKind {
+    TooShort,
+    NotClosed,
+    RepeatedNode(usize),
+    MissingEdge { from: usize, to: usize },
+}
+
+impl Display for GraphError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            GraphError::NodeNotFound(code) => {
+                write!(f, "Nenhum vértice encontrado com o código {code}")
+            }
+            GraphError::NodeAlreadyExists(code) => {
+                write!(f, "Já existe um vértice com o código {code}")
+            }
+            GraphError::EdgeNotFound { from, to } => {
+                write!(f, "A aresta entre {from} e {to} não existe")
+            }
+            GraphError::EdgeAlreadyExists { from, to } => {
+                write!(f, "A aresta entre {from} e {to} já existe")
+            }
+            GraphError::NoPath { from, to } => {
+                write!(
+                    f,
+                    "Não existe caminho entre o vértice {from} e o vértice {to}"
+                )
+            }
+            GraphError::InvalidCycle { position, kind } => {
+                write!(f, "Ciclo inválido na posição {}: {kind}", position + 1)
+            }
+            GraphError::DirectedGraph => {
+                write!(f, "Essa operação não é suportada em grafos direcionados")
+            }
+            GraphError::Parse {
+                line: 0, message, ..
+            } => write!(f, "Erro ao ler o grafo: {message}"),
+            GraphError::Parse {
+                line,
+                column,
+                message,
+            } => write!(
+                f,
+                "Erro ao ler o grafo na linha {line}, coluna {column}: {message}"
+            ),
+            GraphError::Io(err) => write!(f, "Erro ao acessar o arquivo: {err}"),
+            GraphError::UnsupportedFormat(extension) => {
+                write!(f, "O grafo não pode ser salvo no formato {extension}")
+            }
+            GraphError::UnsupportedVersion(version) => write!(
+                f,
+                "O arquivo usa a versão {version} do formato, que é mais nova que a suportada ({})",
+                crate::schema::CURRENT_VERSION
+            ),
+            GraphError::InvalidScaleFactor(factor) => write!(
+                f,
+                "Fator inválido: {factor}, ele precisa ser um número maior que 0"
+            ),
+            GraphError::ScaledWeightOutOfRange { from, to } => write!(
+                f,
+                "O peso da aresta entre {from} e {to} fica fora do intervalo suportado com esse fator"
+            ),
+            GraphError::InvalidWeight(weight) => {
+                write!(f, "Peso inválido: {weight}, ele precisa ser um número diferente de 0")
+            }
+            GraphError::NegativeCycle(codes) => write!(
+                f,
+                "O grafo tem um ciclo de custo negativo, não existe menor caminho: {}",
+                codes
+                    .iter()
+                    .map(usize::to_string)
+                    .collect::<Vec<_>>()
+                    .join(" -> ")
+            ),
+        }
+    }
+}
+
+impl Display for CycleErrorKind {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            CycleErrorKind::TooShort => write!(f, "o ciclo precisa ter pelo menos três vértices"),
+            CycleErrorKind::NotClosed => {
+                write!(f, "o primeiro e o último vértice precisam ser iguais")
+            }
+            CycleErrorKind::RepeatedNode(code) => {
+                write!(f, "o vértice {code} se repete antes do fim do ciclo")
+            }
+            CycleErrorKind::MissingEdge { from, to } => {
+                write!(f, "não existe aresta entre {from} e {to}")
+            }
+        }
+    }
+}
+
+impl Error for GraphError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            GraphError::Io(err) => Some(err),
+            _ => None,
+        }
+    }
+}
+
+impl From<io::Error> for GraphError {
+    fn from(err: io::Error) -> Self {
+        GraphError::Io(err)
+    }
+}
+
+impl From<serde_json::Error> for GraphError {
+    fn from(err: serde_json::Error) -> Self {
+        if err.is_io() {
+            return GraphError::Io(err.into());
+        }
+
+        let (line, column) = (err.line(), err.column());
+        let message = err.to_string();
+        // serde_json already appends the position, which is shown separately
+        let suffix = format!(" at line {line} column {column}");
+
+        GraphError::Parse {
+            line,
+            column,
+            message: message
+                .strip_suffix(&suffix)
+                .unwrap_or(&message)
+                .to_string(),
+        }
+    }
+}
@@ -1,5 +1,8 @@
-use crate::graph::Edge;
 use colored::Colorize;
+use n1_project::{
+    graph_exporter::Backend, node, Attributes, Edge, Graph, GraphError, Node, Weight,
+};
+use std::path::Path;
 
 pub struct Feedback;
 
@@ -8,12 +11,71 @@ impl Feedback {
         print!("\u{1b}[1F");
     }
 
+    pub fn format_node(node: &Node) -> String {
+        let attributes = if node.attributes.is_empty() {
+            String::new()
+        } else {
+            format!(", {}", node::format_attributes(&node.attributes).cyan())
+        };
+
+        format!(
+            "{}Código: {}, Nome: {}, Tipo do local: {}{attributes}{}",
+            "|".green(),
+            node.code.to_string().cyan(),
+            node.name.cyan(),
+            node.local_type.cyan(),
+            "|".green()
+        )
+    }
+
+    pub fn format_graph(graph: &Graph) -> String {
+        let mut string = "** Grafo **\n".blue().bold().to_string();
+
+        for node in graph.nodes() {
+            string = format!("{string}{}\n", Self::format_node(node));
+        }
+
+        if graph.edges().next().is_none() {
+            return string;
+        }
+
+        string.push_str(&"\n** Arestas **".blue().bold().to_string());
+
+        let edges = graph
+            .edges()
+            .map(|edge| Self::format_graph_edge(graph, edge))
+            .collect::<Vec<_>>()
+            .join("\n");
+
+        format!("{string}\n{edges}")
+    }
+
+    /// Edge with its weight, when the graph is weighted, and attributes
+    pub fn format_graph_edge(graph: &Graph, edge: &Edge) -> String {
+        let mut line = Self::format_edge(edge.from, edge.to, graph.is_directed())
+            .cyan()
+            .to_string();
+
+        if graph.is_weighted() {
+            line = format!("{line}  Peso = {}", edge.weight.to_string().cyan());
+        }
+
+        if !edge.attributes.is_empty() {
+            line = format!(
+                "{line}  ({})",
+                node::format_attributes(&edge.attributes).cyan()
+            );
+        }
+
+        line
+    }
+
     pub fn value_read(value: &str, text: &str) -> String {
         Self::clear_line();
         format!("{text}: {}", value.magenta())
     }
 
-    pub fn cycle_read(cycle: &Vec<usize>) -> String {
+    pub fn cycle_read(cycle: &[usize]) -> String {
         Self::clear_line();
 
         let cycle = cycle
@@ -48,6 +110,59 @@ impl Feedback {
         .to_string()
     }
 
+    pub fn read_node_mode() -> String {
+        format!(
+            "{}\n{}) Adicionar vértice\n{}) Renomear vértice\n{}) Remover vértice",
+            "Selecione uma opção:".yellow(),
+            "a".magenta().bold(),
+            "r".magenta().bold(),
+            "d".magenta().bold()
+        )
+    }
+
+    pub fn node_added(code: usize) -> String {
+        format!(
+            "{} {}",
+            "Vértice adicionado com sucesso:".green(),
+            code.to_string().cyan()
+        )
+    }
+
+    pub fn node_renamed(code: usize) -> String {
+        format!(
+            "{} {}",
+            "Vértice renomeado com sucesso:".green(),
+            code.to_string().cyan()
+        )
+    }
+
+    pub fn node_removed(code: usize, removed_edges: usize) -> String {
+        format!(
+            "{} {}, {} aresta(s) incidente(s) também removida(s)",
+            "Vértice removido com sucesso:".green(),
+            code.to_string().cyan(),
+            removed_edges.to_string().cyan()
+        )
+    }
+
+    pub fn last_node_not_removed() -> String {
+        format!(
+            "{}",
+            "O grafo deve ter pelo menos um vértice, o último não pode ser removido!".red()
+        )
+    }
+
+    pub fn graph_without_nodes() -> String {
+        format!(
+            "{}",
+            "O grafo não tem vértices, adicione um com a opção r!".red()
+        )
+    }
+
+    pub fn graph_size(size: usize) -> String {
+        format!("O grafo possui {} vértice(s)", size.to_string().cyan())
+    }
+
     pub fn invalid_code() -> String {
         format!("{}", "Por favor, digite um código válido.".red())
     }
@@ -73,41 +188,420 @@ impl Feedback {
     }
 
     pub fn read_weight() -> String {
-        format!("{}", "Digite o peso da aresta:".yellow())
+        format!(
+            "{}",
+            "Digite o peso da aresta (ex.: 5, 2.5 ou -3):".yellow()
+        )
     }
 
-    pub fn invalid_weight() -> String {
+    pub fn read_path_mode() -> String {
+        format!(
+            "{}\n{}) Profundidade (primeiro caminho encontrado)\n{}) Largura (caminho com menos arestas)",
+            "Selecione o modo de busca:".yellow(),
+            "p".magenta().bold(),
+            "l".magenta().bold()
+        )
+    }
+
+    pub fn read_max_length() -> String {
         format!(
             "{}",
-            "Peso inválido, ele deve ser um inteiro maior que 0".red()
+            "Digite o número máximo de arestas do caminho (deixe vazio para não limitar):".yellow()
         )
     }
 
-    pub fn invalid_cycle() -> String {
-        format!("{}", "Certifique-se de digitar um ciclo válido, o primeiro e o último elemento precisam ser iguais, e não pode haver elementos repetidos entre eles".red())
+    pub fn read_max_weight() -> String {
+        format!(
+            "{}",
+            "Digite o custo máximo do caminho (deixe vazio para não limitar):".yellow()
+        )
+    }
+
+    pub fn invalid_limit() -> String {
+        format!(
+            "{}",
+            "Limite inválido, ele deve ser um inteiro positivo".red()
+        )
+    }
+
+    pub fn invalid_weight() -> String {
+        format!(
+            "{}",
+            "Peso inválido, ele deve ser um número diferente de 0".red()
+        )
     }
 
     pub fn available_nodes() -> String {
-        format!("Vértices disponíveis:")
+        "Vértices disponíveis:".to_string()
     }
 
     pub fn load_graph_success() -> String {
         format!("{}", "Grafo carregado com sucesso!".green())
     }
 
-    pub fn read_graph_file_error() -> String {
+    pub fn read_graph_file_error(err: &GraphError) -> String {
+        format!(
+            "{}\n{}",
+            "Erro ao ler arquivo, verifique se o grafo foi salvo e se o arquivo existe".red(),
+            Self::graph_error(err)
+        )
+    }
+
+    pub fn save_graph_success(file: &Path) -> String {
+        let text = format!("{}", "Grafo salvo com sucesso!".green());
+        format!("{text} Arquivo: {}", file.display())
+    }
+
+    pub fn read_csv_mode() -> String {
+        format!(
+            "{}\n{}) Importar vértices e arestas (code,name,local_type e from,to,weight)\n{}) Importar matriz de adjacência\n{}) Exportar vértices e arestas\n{}) Exportar matriz de adjacência",
+            "Escolha a operação:".yellow(),
+            "i".magenta().bold(),
+            "m".magenta().bold(),
+            "e".magenta().bold(),
+            "x".magenta().bold(),
+        )
+    }
+
+    pub fn read_attribute_mode() -> String {
+        format!(
+            "{}\n{}) Definir ou remover atributo de um vértice\n{}) Definir ou remover atributo de uma aresta\n{}) Consultar vértices e arestas por atributo",
+            "Escolha a operação:".yellow(),
+            "v".magenta().bold(),
+            "a".magenta().bold(),
+            "c".magenta().bold(),
+        )
+    }
+
+    pub fn read_attribute_key() -> String {
         format!(
             "{}",
-            "Erro ao ler arquivo, verifique se o grafo foi salvo e se o arquivo existe".red()
+            "Digite o nome do atributo (ex.: capacidade, andar):".yellow()
+        )
+    }
+
+    pub fn read_attribute_value() -> String {
+        format!(
+            "{}",
+            "Digite o valor do atributo, ou deixe vazio para remover:".yellow()
+        )
+    }
+
+    pub fn read_attribute_query() -> String {
+        format!(
+            "{}",
+            "Digite o atributo a consultar, ou atributo=valor para filtrar pelo valor:".yellow()
+        )
+    }
+
+    pub fn invalid_attribute_key() -> String {
+        format!("{}", "Por favor, digite o nome do atributo.".red())
+    }
+
+    pub fn current_attributes(attributes: &Attributes) -> String {
+        if attributes.is_empty() {
+            return "Nenhum atributo definido".to_string();
+        }
+
+        format!(
+            "Atributos atuais: {}",
+            node::format_attributes(attributes).cyan()
+        )
+    }
+
+    pub fn available_attributes(keys: &[&str]) -> String {
+        format!("Atributos em uso: {}", keys.join(", ").cyan())
+    }
+
+    pub fn no_attributes() -> String {
+        format!("{}", "Nenhum vértice ou aresta possui atributos".red())
+    }
+
+    pub fn attribute_set(key: &str, value: &str) -> String {
+        format!(
+            "{} {} = {}",
+            "Atributo definido com sucesso:".green(),
+            key.cyan(),
+            value.cyan()
         )
     }
 
-    pub fn save_graph_success() -> String {
-        format!("{}", "Grafo salvo com sucesso!".green())
+    pub fn attribute_removed(key: &str) -> String {
+        format!(
+            "{} {}",
+            "Atributo removido com sucesso:".green(),
+            key.cyan()
+        )
+    }
+
+    pub fn attribute_not_set(key: &str) -> String {
+        format!("{} {}", "O atributo não está definido:".red(), key.cyan())
+    }
+
+    pub fn attribute_query_result(nodes: &[String], edges: &[String]) -> String {
+        if nodes.is_empty() && edges.is_empty() {
+            return format!("{}", "Nenhum vértice ou aresta encontrado".yellow());
+        }
+
+        let mut result = String::new();
+
+        if !nodes.is_empty() {
+            result.push_str(&format!(
+                "{}\n{}",
+                "** Vértices **".blue().bold(),
+                nodes.join("\n")
+            ));
+        }
+
+        if !edges.is_empty() {
+            if !result.is_empty() {
+                result.push_str("\n\n");
+            }
+
+            result.push_str(&format!(
+                "{}\n{}",
+                "** Arestas **".blue().bold(),
+                edges.join("\n")
+            ));
+        }
+
+        result
+    }
+
+    pub fn read_history_mode() -> String {
+        format!(
+            "{}\n{}) Desfazer a última alteração\n{}) Refazer a última alteração desfeita\n{}) Listar as alterações desde o último salvamento",
+            "Escolha a operação:".yellow(),
+            "d".magenta().bold(),
+            "r".magenta().bold(),
+            "a".magenta().bold(),
+        )
     }
 
-    pub fn save_graph_error() -> String {
-        format!("{}", "Erro ao salvar arquivo :(".red())
+    pub fn change_undone(description: &str) -> String {
+        format!("{} {}", "Alteração desfeita:".green(), description.cyan())
+    }
+
+    pub fn change_redone(description: &str) -> String {
+        format!("{} {}", "Alteração refeita:".green(), description.cyan())
+    }
+
+    pub fn nothing_to_undo() -> String {
+        format!("{}", "Não há alterações para desfazer".red())
+    }
+
+    pub fn nothing_to_redo() -> String {
+        format!("{}", "Não há alterações para refazer".red())
+    }
+
+    pub fn changes_log(log: &[String]) -> String {
+        if log.is_empty() {
+            return format!("{}", "Nenhuma alteração desde o último salvamento".yellow());
+        }
+
+        let entries = log
+            .iter()
+            .enumerate()
+            .map(|(i, entry)| format!("{}. {entry}", i + 1))
+            .collect::<Vec<_>>()
+            .join("\n");
+
+        format!(
+            "{}\n{entries}",
+            "** Alterações desde o último salvamento **".blue().bold()
+        )
+    }
+
+    pub fn read_nodes_csv() -> String {
+        format!("{}", "** CSV dos vértices **".blue().bold())
+    }
+
+    pub fn read_edges_csv() -> String {
+        format!("{}", "** CSV das arestas **".blue().bold())
+    }
+
+    pub fn read_matrix_csv() -> String {
+        format!("{}", "** CSV da matriz de adjacência **".blue().bold())
+    }
+
+    pub fn read_is_directed() -> String {
+        format!("{}", "O grafo é direcionado? (s/n)".yellow())
+    }
+
+    pub fn read_is_weighted() -> String {
+        format!("{}", "O grafo é ponderado? (s/n)".yellow())
+    }
+
+    pub fn csv_exported(paths: &[&Path]) -> String {
+        let paths = paths
+            .iter()
+            .map(|path| format!("- {}", path.display()))
+            .collect::<Vec<_>>()
+            .join("\n");
+
+        format!("{}\n{paths}", "Arquivos exportados com sucesso!".green())
+    }
+
+    pub fn graph_imported(file: &Path) -> String {
+        format!(
+            "{} Ele será salvo em: {}",
+            "Grafo importado!".green(),
+            file.display()
+        )
+    }
+
+    pub fn save_format_not_supported() -> String {
+        format!(
+            "{}",
+            "O grafo só pode ser salvo em JSON, GraphML ou GEXF, use a opção x para exportá-lo em outros formatos"
+                .red()
+        )
+    }
+
+    pub fn graph_not_saved() -> String {
+        format!("{}", "O grafo não foi salvo".yellow())
+    }
+
+    pub fn confirm_overwrite_file(file: &Path) -> String {
+        format!(
+            "{}",
+            format!(
+                "O arquivo {} já existe, deseja sobrescrevê-lo? (s/n)",
+                file.display()
+            )
+            .yellow()
+        )
+    }
+
+    pub fn confirm_save_import_over(file: &Path) -> String {
+        format!(
+            "{}",
+            format!(
+                "O arquivo {} já existe, deseja salvar o grafo importado nele? (s/n)",
+                file.display()
+            )
+            .yellow()
+        )
+    }
+
+    pub fn read_import_save_path() -> String {
+        format!(
+            "{}",
+            "** Arquivo onde o grafo importado será salvo **"
+                .blue()
+                .bold()
+        )
+    }
+
+    pub fn current_file(file: &Path, is_dirty: bool) -> String {
+        let unsaved = if is_dirty {
+            format!(" {}", "(alterações não salvas)".yellow())
+        } else {
+            String::new()
+        };
+
+        format!(
+            "Arquivo atual: {}{unsaved}",
+            file.display().to_string().cyan()
+        )
+    }
+
+    pub fn confirm_discard_changes() -> String {
+        format!(
+            "{}",
+            "O grafo tem alterações não salvas, deseja descartá-las? (s/n)".yellow()
+        )
+    }
+
+    pub fn graph_not_replaced() -> String {
+        format!(
+            "{}",
+            "O grafo atual foi mantido, use a opção s para salvá-lo".yellow()
+        )
+    }
+
+    pub fn confirm_restore_recovery(file: &Path) -> String {
+        format!(
+            "{}",
+            format!(
+                "Encontramos alterações não salvas de uma sessão anterior em {}, deseja restaurá-las? (s/n)",
+                file.display()
+            )
+            .yellow()
+        )
+    }
+
+    pub fn recovery_restored() -> String {
+        format!(
+            "{}",
+            "Alterações restauradas! Use a opção s para salvá-las".green()
+        )
+    }
+
+    pub fn autosave_enabled(file: &Path) -> String {
+        format!(
+            "{} As alterações não salvas serão copiadas para: {}",
+            "Salvamento automático ativado!".green(),
+            file.display()
+        )
+    }
+
+    pub fn autosave_disabled() -> String {
+        format!("{}", "Salvamento automático desativado".yellow())
+    }
+
+    pub fn autosave_error(file: &Path, err: &GraphError) -> String {
+        format!(
+            "{}\n{}",
+            format!("Erro ao salvar as alterações em {} :(", file.display()).red(),
+            Self::graph_error(err)
+        )
+    }
+
+    pub fn read_file_path(default_extension: &str, optional: bool) -> String {
+        let optional = if optional {
+            ", ou deixe vazio para pular"
+        } else {
+            ""
+        };
+
+        format!(
+            "{}",
+            format!(
+                "Digite o caminho do arquivo (sem extensão usa .{default_extension}){optional}:"
+            )
+            .yellow()
+        )
+    }
+
+    pub fn invalid_file_path() -> String {
+        format!("{}", "Caminho inválido, digite novamente".red())
+    }
+
+    pub fn available_files(files: &[String]) -> String {
+        if files.is_empty() {
+            return format!("{}", "Nenhum arquivo compatível na pasta atual".yellow());
+        }
+
+        let files = files
+            .iter()
+            .map(|file| format!("- {}", file.magenta()))
+            .collect::<Vec<_>>()
+            .join("\n");
+
+        format!("{}\n{files}", "Arquivos na pasta atual:".blue())
+    }
+
+    pub fn save_graph_error(err: &GraphError) -> String {
+        format!(
+            "{}\n{}",
+            "Erro ao salvar arquivo :(".red(),
+            Self::graph_error(err)
+        )
+    }
+
+    pub fn graph_error(err: &GraphError) -> String {
+        err.to_string().red().to_string()
     }
 
     pub fn no_buckle(code: usize) -> String {
@@ -130,6 +624,21 @@ impl Feedback {
         format!("{}", "Caminho encontrado!".green())
     }
 
+    pub fn shortest_path_found() -> String {
+        format!("{}", "Menor caminho encontrado!".green())
+    }
+
+    pub fn paths_found(count: usize) -> String {
+        format!(
+            "{} caminho(s) encontrado(s), ordenados pelo custo:",
+            count.to_string().green()
+        )
+    }
+
+    pub fn path_cost(cost: Weight) -> String {
+        format!("[custo {}]", cost.to_string().cyan())
+    }
+
     pub fn no_path_found(code1: usize, code2: usize) -> String {
         format!(
             "{} existe caminho entre o vértice {} e o vértice {}",
@@ -139,19 +648,19 @@ impl Feedback {
         )
     }
 
-    pub fn edge_added(edge: Edge) -> String {
+    pub fn edge_added(edge: Edge, directed: bool) -> String {
         format!(
             "{}\n{}",
             "Aresta criada com sucesso".green(),
-            Self::format_edge(edge.from, edge.to)
+            Self::format_edge(edge.from, edge.to, directed)
         )
     }
 
-    pub fn edge_removed(edge1: usize, edge2: usize) -> String {
+    pub fn edge_removed(edge1: usize, edge2: usize, directed: bool) -> String {
         format!(
             "{} {} {}",
             "Aresta".green(),
-            Self::format_edge(edge1, edge2),
+            Self::format_edge(edge1, edge2, directed),
             "removida com sucesso".green()
         )
     }
@@ -178,30 +687,120 @@ impl Feedback {
         format!("{}", "Ciclo encontrado!".green())
     }
 
+    pub fn cycles_found(count: usize) -> String {
+        format!("{} ciclo(s) encontrado(s):", count.to_string().green())
+    }
+
+    pub fn graph_is_acyclic() -> String {
+        format!("O grafo {} acíclico", "é".green())
+    }
+
+    pub fn graph_is_not_acyclic() -> String {
+        format!("O grafo {} acíclico", "não é".red())
+    }
+
+    pub fn read_cycle_mode() -> String {
+        format!(
+            "{}\n{}) Listar todos os ciclos\n{}) Listar os ciclos que passam por um vértice\n{}) Verificar um ciclo digitado",
+            "Selecione uma opção:".yellow(),
+            "t".magenta().bold(),
+            "v".magenta().bold(),
+            "d".magenta().bold()
+        )
+    }
+
     pub fn no_cycle_found() -> String {
         format!("Ciclo {} encontrado", "não".red())
     }
 
-    pub fn edge_already_exists(edge: &Edge) -> String {
-        format!("Aresta {} já existe", Self::format_edge(edge.from, edge.to))
-            .red()
-            .to_string()
+    pub fn edge_is_bridge(edge1: usize, edge2: usize, directed: bool) -> String {
+        format!(
+            "{} a aresta {} é uma ponte, removê-la vai desconectar o grafo",
+            "Atenção:".yellow().bold(),
+            Self::format_edge(edge1, edge2, directed)
+        )
     }
 
-    pub fn edge_dont_exists() -> String {
-        format!("A aresta informada não existe")
+    pub fn confirm_remove_bridge() -> String {
+        format!("{}", "Deseja remover a aresta mesmo assim? (s/n)".yellow())
     }
 
-    pub fn format_edge(edge1: usize, edge2: usize) -> String {
-        format!("{edge1} <-> {edge2}")
+    pub fn edge_not_removed() -> String {
+        format!("{}", "A aresta não foi removida".yellow())
+    }
+
+    pub fn bridges_title() -> String {
+        format!("{}", "** Pontes **".blue().bold())
+    }
+
+    pub fn no_bridges() -> String {
+        "O grafo não possui pontes".to_string()
+    }
+
+    pub fn articulation_points_title() -> String {
+        format!("{}", "** Pontos de articulação **".blue().bold())
+    }
+
+    pub fn no_articulation_points() -> String {
+        "O grafo não possui pontos de articulação".to_string()
+    }
+
+    pub fn format_edge(edge1: usize, edge2: usize, directed: bool) -> String {
+        if directed {
+            format!("{edge1} -> {edge2}")
+        } else {
+            format!("{edge1} <-> {edge2}")
+        }
     }
 
     pub fn success_graph_weighted() -> String {
         format!("{}", "Grafo ponderado com sucesso!".green())
     }
 
-    pub fn graph_already_weighted() -> String {
-        format!("{}", "O grafo já é ponderado".red())
+    pub fn success_graph_unweighted() -> String {
+        format!(
+            "{}",
+            "Grafo tornado não ponderado, os pesos voltaram a 1".green()
+        )
+    }
+
+    pub fn read_weight_mode() -> String {
+        format!(
+            "{}\n{}) Alterar o peso de uma aresta\n{}) Multiplicar todos os pesos por um fator\n{}) Tornar o grafo não ponderado",
+            "Escolha a operação:".yellow(),
+            "e".magenta().bold(),
+            "m".magenta().bold(),
+            "n".magenta().bold(),
+        )
+    }
+
+    pub fn read_scale_factor() -> String {
+        format!("{}", "Digite o fator (ex.: 2 ou 0.5):".yellow())
+    }
+
+    pub fn invalid_scale_factor() -> String {
+        format!(
+            "{}",
+            "Fator inválido, ele deve ser um número maior que 0".red()
+        )
+    }
+
+    pub fn weights_scaled(factor: f64) -> String {
+        format!(
+            "{} {}",
+            "Pesos multiplicados por".green(),
+            factor.to_string().cyan()
+        )
+    }
+
+    pub fn weight_changed(from: usize, to: usize, weight: Weight, directed: bool) -> String {
+        format!(
+            "{} {} {} {}",
+            "Peso da aresta".green(),
+            Self::format_edge(from, to, directed),
+            "alterado para".green(),
+            weight.to_string().cyan()
+        )
     }
 
     pub fn graph_is_complete() -> String {
@@ -212,12 +811,75 @@ impl Feedback {
         format!("O grafo {} completo", "não é".red())
     }
 
+    pub fn graph_is_connected() -> String {
+        format!("O grafo {} conexo", "é".green())
+    }
+
+    pub fn graph_is_not_connected(components: usize) -> String {
+        format!(
+            "O grafo {} conexo, ele possui {} componentes",
+            "não é".red(),
+            components.to_string().cyan()
+        )
+    }
+
+    pub fn component_title(num: usize) -> String {
+        let title = format!("** Componente {num} **");
+        format!("{}", title.blue().bold())
+    }
+
     pub fn graph_is_not_weighted() -> String {
         format!("{}", "O grafo precisa ser ponderado".red())
     }
 
-    pub fn path_size(size: u32) -> String {
-        format!("O tamanho do caminho é: {}", size.to_string().green())
+    pub fn minimum_spanning_tree_found() -> String {
+        format!("{}", "Árvore geradora mínima encontrada!".green())
+    }
+
+    pub fn minimum_spanning_tree_cost(cost: Weight) -> String {
+        format!("O custo total da árvore é: {}", cost.to_string().green())
+    }
+
+    pub fn read_export_highlight(subject: &str) -> String {
+        format!(
+            "{}",
+            format!("Deseja exportar o grafo com {subject} em destaque? (s/n)").yellow()
+        )
+    }
+
+    pub fn read_dot_format() -> String {
+        format!(
+            "{}\n{}) PNG\n{}) SVG\n{}) PDF",
+            "Escolha o formato gerado pelo graphviz:".yellow(),
+            "p".magenta().bold(),
+            "s".magenta().bold(),
+            "d".magenta().bold(),
+        )
+    }
+
+    pub fn read_color_by_type() -> String {
+        format!(
+            "{}",
+            "Deseja colorir os vértices pelo tipo do local? (s/n)".yellow()
+        )
+    }
+
+    pub fn read_shape_by_type() -> String {
+        format!(
+            "{}",
+            "Deseja usar um formato de vértice para cada tipo do local? (s/n)".yellow()
+        )
+    }
+
+    pub fn read_scale_by_weight() -> String {
+        format!(
+            "{}",
+            "Deseja que arestas mais pesadas sejam mais longas e grossas? (s/n)".yellow()
+        )
+    }
+
+    pub fn path_size(size: Weight) -> String {
+        format!("O custo do menor caminho é: {}", size.to_string().green())
     }
 
     pub fn is_subgraph() -> String {
@@ -233,10 +895,26 @@ impl Feedback {
         format!("{text} Arquivo: {path}")
     }
 
-    pub fn graph_not_exported() -> String {
+    pub fn read_export_backend() -> String {
+        format!(
+            "{}\n{}) SVG, posicionando os vértices por forças\n{}) SVG, com os vértices em círculo\n{}) PNG, SVG ou PDF, usando o graphviz",
+            "Escolha o formato da exportação:".yellow(),
+            "f".magenta().bold(),
+            "c".magenta().bold(),
+            "g".magenta().bold(),
+        )
+    }
+
+    pub fn graph_not_exported(backend: Backend, err: &str) -> String {
+        let text = format!("{}: {err}", "Erro ao exportar grafo".red());
+
+        if !matches!(backend, Backend::Graphviz(_)) {
+            return format!("{text}\nVerifique se é possivel criar arquivos na pasta do grafo");
+        }
+
         let executable = format!("{}", "graphviz".green());
         let link = format!("{}", "https://graphviz.org/".cyan());
 
-        format!("{}\nVerifique se você possui o programa {executable} ({link}) instalado e se é possivel criar arquivos na pasta atual", "Erro ao exportar grafo".red())
+        format!("{text}\nVerifique se você possui o programa {executable} ({link}) instalado, ou exporte como SVG")
     }
 }
@@ -0,0 +1,90 @@
+//! Graphs shared by the unit tests
+
+use crate::{
+    graph::{Edge, Graph},
+    node::{Attributes, Node},
+    weight::Weight,
+};
+
+/// Weighted graph with the nodes named after their code and the edges given
+/// as `(from, to, weight)`
+pub fn build(is_directed: bool, codes: &[usize], edges: &[(usize, usize, i64)]) -> Graph {
+    let mut graph = Graph::new(true, is_directed);
+
+    for &code in codes {
+        graph
+            .add_node(Node {
+                code,
+                name: code.to_string(),
+                local_type: String::new(),
+                attributes: Attributes::new(),
+            })
+            .unwrap();
+    }
+
+    for &(from, to, weight) in edges {
+        graph
+            .add_edge(Edge {
+                from,
+                to,
+                weight: Weight::Integer(weight),
+                attributes: Attributes::new(),
+            })
+            .unwrap();
+    }
+
+    graph
+}
+
+pub fn codes(nodes: &[&Node]) -> Vec<usize> {
+    nodes.iter().map(|node| node.code).collect()
+}
+
+/// Weighted graph with names that need quoting or escaping, local types,
+/// attributes, a float and a negative weight and a buckle
+pub fn campus(is_directed: bool) -> Graph {
+    let mut graph = Graph::new(true, is_directed);
+
+    let nodes = [
+        (1, "Sala \"A\", <1> & 2", "sala"),
+        (2, "Pátio", "área"),
+        (5, "Portão", ""),
+    ];
+
+    for (code, name, local_type) in nodes {
+        graph
+            .add_node(Node {
+                code,
+                name: name.to_string(),
+                local_type: local_type.to_string(),
+                attributes: Attributes::new(),
+            })
+            .unwrap();
+    }
+
+    let edges = [
+        (1, 2, Weight::Float(2.5)),
+        (2, 5, Weight::Integer(-3)),
+        (5, 5, Weight::ONE),
+    ];
+
+    for (from, to, weight) in edges {
+        graph
+            .add_edge(Edge {
+                from,
+                to,
+                weight,
+                attributes: Attributes::new(),
+            })
+            .unwrap();
+    }
+
+    graph
+        .set_node_attribute(1, "andar".to_string(), "2".to_string())
+        .unwrap();
+    graph
+        .set_edge_attribute(1, 2, "coberta".to_string(), "sim".to_string())
+        .unwrap();
+
+    graph
+}
@@ -0,0 +1,340 @@
+use std::collections::HashMap;
+
+use crate::{
+    error::GraphError,
+    graph::{Edge, Graph},
+    graph_importer,
+    node::{Attributes, Node},
+    weight::Weight,
+    xml::{self, escape},
+};
+
+/// Id of the edge attribute declared by weighted graphs, the other attributes
+/// have ids like `edge.<key>`
+const WEIGHT_ID: &str = "weight";
+
+/// GEXF 1.3 document of the graph, the node name is the `label` and the
+/// `local_type` and the other attributes are node or edge attributes. Weights
+/// are only written for weighted graphs, which also declare an edge attribute
+/// with id `weight` so they're still weighted without edges
+pub fn to_gexf(graph: &Graph) -> String {
+    let edge_type = if graph.is_directed() {
+        "directed"
+    } else {
+        "undirected"
+    };
+
+    let mut node_keys: Vec<&str> = graph
+        .nodes()
+        .flat_map(|node| node.attributes.keys())
+        .map(String::as_str)
+        .collect();
+    node_keys.sort_unstable();
+    node_keys.dedup();
+
+    let mut edge_keys: Vec<&str> = graph
+        .edges()
+        .flat_map(|edge| edge.attributes.keys())
+        .map(String::as_str)
+        .collect();
+    edge_keys.sort_unstable();
+    edge_keys.dedup();
+
+    let mut f = String::from(
+        "<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n<gexf xmlns=\"http://gexf.net/1.3\" version=\"1.3\">\n",
+    );
+
+    f.push_str(&format!(
+        "  <graph mode=\"static\" defaultedgetype=\"{edge_type}\">\n"
+    ));
+    f.push_str("    <attributes class=\"node\">\n      <attribute id=\"local_type\" title=\"local_type\" type=\"string\"/>\n");
+
+    for key in &node_keys {
+        f.push_str(&attribute_declaration("node", key));
+    }
+
+    f.push_str("    </attributes>\n");
+
+    if graph.is_weighted() || !edge_keys.is_empty() {
+        f.push_str("    <attributes class=\"edge\">\n");
+
+        if graph.is_weighted() {
+            f.push_str(&format!(
+                "      <attribute id=\"{WEIGHT_ID}\" title=\"weight\" type=\"double\"/>\n"
+            ));
+        }
+
+        for key in &edge_keys {
+            f.push_str(&attribute_declaration("edge", key));
+        }
+
+        f.push_str("    </attributes>\n");
+    }
+
+    f.push_str("    <nodes>\n");
+
+    for node in graph.nodes() {
+        f.push_str(&format!(
+            "      <node id=\"{}\" label=\"{}\">\n        <attvalues>\n          <attvalue for=\"local_type\" value=\"{}\"/>\n",
+            node.code,
+            escape(&node.name),
+            escape(&node.local_type)
+        ));
+        f.push_str(&attribute_values("node", &node.attributes));
+        f.push_str("        </attvalues>\n      </node>\n");
+    }
+
+    f.push_str("    </nodes>\n    <edges>\n");
+
+    for (id, edge) in graph.edges().enumerate() {
+        f.push_str(&format!(
+            "      <edge id=\"{id}\" source=\"{}\" target=\"{}\"",
+            edge.from, edge.to
+        ));
+
+        if graph.is_weighted() {
+            f.push_str(&format!(" weight=\"{}\"", edge.weight));
+        }
+
+        if edge.attributes.is_empty() {
+            f.push_str("/>\n");
+        } else {
+            f.push_str(">\n        <attvalues>\n");
+            f.push_str(&attribute_values("edge", &edge.attributes));
+            f.push_str("        </attvalues>\n      </edge>\n");
+        }
+    }
+
+    f.push_str("    </edges>\n  </graph>\n</gexf>\n");
+
+    f
+}
+
+/// Builds a graph from a GEXF document.
+///
+/// The node `label` is used as the name and the node attribute titled
+/// `local_type` (or `type`) as the `local_type`, the other node and edge
+/// attributes are kept by their title. Numeric node ids are used as codes, the
+/// others get the next free codes. The graph is weighted when an edge
+/// attribute with id `weight` is declared or any edge has a `weight`, edges
+/// without one get weight 1
+pub fn parse_gexf(source: &str) -> Result<Graph, GraphError> {
+    let root = xml::parse(source)?;
+
+    if root.local_name() != "gexf" {
+        return Err(root.error("esperado o elemento <gexf>"));
+    }
+
+    let graph_element = root
+        .child("graph")
+        .ok_or_else(|| root.error("esperado o elemento <graph>"))?;
+
+    let is_directed = graph_element.attribute("defaultedgetype") == Some("directed");
+
+    // Maps the attribute id to its title, by class
+    let mut node_attributes: HashMap<&str, &str> = HashMap::new();
+    let mut edge_attributes: HashMap<&str, &str> = HashMap::new();
+
+    for class in graph_element.children_named("attributes") {
+        let titles = match class.attribute("class") {
+            Some("node") => &mut node_attributes,
+            Some("edge") => &mut edge_attributes,
+            _ => continue,
+        };
+
+        for attribute in class.children_named("attribute") {
+            let id = attribute.required_attribute("id")?;
+            titles.insert(id, attribute.attribute("title").unwrap_or(id));
+        }
+    }
+
+    let node_elements: Vec<&xml::Element> = graph_element
+        .child("nodes")
+        .map(|nodes| nodes.children_named("node").collect())
+        .unwrap_or_default();
+    let edge_elements: Vec<&xml::Element> = graph_element
+        .child("edges")
+        .map(|edges| edges.children_named("edge").collect())
+        .unwrap_or_default();
+
+    let mut ids = Vec::new();
+
+    for node in &node_elements {
+        ids.push(node.required_attribute("id")?);
+    }
+
+    let codes = graph_importer::assign_codes(ids.iter().copied());
+
+    // Files written before the declared weight have the marker on <graph>
+    let is_weighted = edge_attributes.contains_key(WEIGHT_ID)
+        || graph_element.attribute("weighted") == Some("true")
+        || edge_elements
+            .iter()
+            .any(|edge| edge.attribute("weight").is_some());
+
+    let mut graph = Graph::new(is_weighted, is_directed);
+
+    for (node, id) in node_elements.iter().zip(&ids) {
+        let mut attributes = attribute_values_of(node, &node_attributes);
+        let local_type = attributes
+            .remove("local_type")
+            .or_else(|| attributes.remove("type"))
+            .unwrap_or_default();
+
+        graph.add_node(Node {
+            code: codes[*id],
+            name: node.attribute("label").unwrap_or(id).to_string(),
+            local_type,
+            attributes,
+        })?;
+    }
+
+    for edge in edge_elements {
+        let code_of = |attribute: &str| -> Result<usize, GraphError> {
+            let id = edge.required_attribute(attribute)?;
+
+            codes.get(id).copied().ok_or_else(|| {
+                edge.error(&format!(
+                    "a aresta usa o vértice {id}, que não foi declarado"
+                ))
+            })
+        };
+
+        let weight = match edge.attribute("weight") {
+            Some(weight) => graph_importer::parse_weight(weight).ok_or_else(|| {
+                edge.error(&format!(
+                    "o peso da aresta precisa ser um número diferente de 0: {weight}"
+                ))
+            })?,
+            None => Weight::ONE,
+        };
+
+        graph.add_edge(Edge {
+            from: code_of("source")?,
+            to: code_of("target")?,
+            weight,
+            attributes: attribute_values_of(edge, &edge_attributes),
+        })?;
+    }
+
+    Ok(graph)
+}
+
+fn attribute_declaration(class: &str, key: &str) -> String {
+    format!(
+        "      <attribute id=\"{class}.{0}\" title=\"{0}\" type=\"string\"/>\n",
+        escape(key)
+    )
+}
+
+fn attribute_values(class: &str, attributes: &Attributes) -> String {
+    attributes
+        .iter()
+        .map(|(key, value)| {
+            format!(
+                "          <attvalue for=\"{class}.{}\" value=\"{}\"/>\n",
+                escape(key),
+                escape(value)
+            )
+        })
+        .collect()
+}
+
+/// `<attvalue>`s of the element by the title of their attribute
+fn attribute_values_of(element: &xml::Element, titles: &HashMap<&str, &str>) -> Attributes {
+    element
+        .child("attvalues")
+        .into_iter()
+        .flat_map(|values| values.children_named("attvalue"))
+        .filter_map(|value| {
+            let id = value.attribute("for")?;
+            let title = titles.get(id).copied().unwrap_or(id);
+
+            Some((title.to_string(), value.attribute("value")?.to_string()))
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::fixtures::campus;
+
+    #[test]
+    fn round_trips_the_graph() {
+        for is_directed in [false, true] {
+            let graph = campus(is_directed);
+            let parsed = parse_gexf(&to_gexf(&graph)).unwrap();
+
+            assert_eq!(parsed.to_json(), graph.to_json());
+        }
+    }
+
+    #[test]
+    fn reads_attributes_by_title_and_gephi_weights() {
+        let source = r#"<?xml version="1.0" encoding="UTF-8"?>
+            <gexf xmlns="http://gexf.net/1.3" version="1.3">
+              <graph defaultedgetype="directed">
+                <attributes class="node">
+                  <attribute id="0" title="type" type="string"/>
+                  <attribute id="1" title="andar" type="integer"/>
+                </attributes>
+                <nodes>
+                  <node id="n0" label="Sala &quot;A&quot;">
+                    <attvalues><attvalue for="0" value="sala"/><attvalue for="1" value="2"/></attvalues>
+                  </node>
+                  <node id="3" label="Pátio"/>
+                </nodes>
+                <edges>
+                  <edge id="0" source="n0" target="3" weight="5.0"/>
+                  <edge id="1" source="3" target="n0"/>
+                </edges>
+              </graph>
+            </gexf>"#;
+
+        let graph = parse_gexf(source).unwrap();
+        let sala = graph.find_by_code(4).unwrap();
+
+        assert!(graph.is_directed() && graph.is_weighted());
+        assert_eq!(sala.name, "Sala \"A\"");
+        assert_eq!(sala.local_type, "sala");
+        assert_eq!(sala.attributes["andar"], "2");
+        assert_eq!(
+            graph.find_edge_by_from_to(4, 3).unwrap().weight,
+            Weight::Integer(5)
+        );
+        assert_eq!(
+            graph.find_edge_by_from_to(3, 4).unwrap().weight,
+            Weight::ONE
+        );
+    }
+
+    #[test]
+    fn rejects_edges_to_undeclared_nodes() {
+        let source = r#"<gexf><graph><nodes><node id="1"/></nodes>
+            <edges><edge source="1" target="2"/></edges></graph></gexf>"#;
+
+        assert!(parse_gexf(source).is_err());
+        assert!(parse_gexf("<graphml/>").is_err());
+    }
+
+    #[test]
+    fn keeps_graphs_without_edges_weighted() {
+        let mut graph = Graph::new(true, false);
+        graph
+            .add_node(campus(false).find_by_code(5).unwrap().clone())
+            .unwrap();
+
+        let gexf = to_gexf(&graph);
+
+        assert!(gexf.contains(r#"<attribute id="weight" title="weight" type="double"/>"#));
+        assert!(parse_gexf(&gexf).unwrap().is_weighted());
+        assert!(!parse_gexf(&to_gexf(&Graph::new(false, false)))
+            .unwrap()
+            .is_weighted());
+
+        let old = r#"<gexf><graph weighted="true"><nodes><node id="1"/></nodes></graph></gexf>"#;
+
+        assert!(parse_gexf(old).unwrap().is_weighted());
+    }
+}
@@ -1,239 +1,1194 @@
-use crate::node::Node;
-use colored::Colorize;
-use serde::{Deserialize, Serialize};
-use std::fmt::Display;
-
-pub enum GraphError {
-    EdgeAlreadyExists,
-    EdgeDontExists,
+use crate::{
+    error::{CycleErrorKind, GraphError},
+    node::{self, Attributes, Node},
+    schema,
+    weight::Weight,
+};
+use petgraph::{
+    stable_graph::{EdgeIndex, NodeIndex, StableDiGraph},
+    visit::EdgeRef,
+    Direction::{Incoming, Outgoing},
+};
+use serde::{de, Deserialize, Deserializer, Serialize, Serializer};
+use std::{
+    cmp::Reverse,
+    collections::{BinaryHeap, HashMap, HashSet, VecDeque},
+    fmt::Display,
+    fs,
+    path::Path,
+};
+
+/// Search strategy used by `Graph::get_path`
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum PathMode {
+    /// Depth-first search, returns the first path found
+    Depth,
+    /// Breadth-first search, returns the path with the fewest edges
+    Breadth,
 }
 
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct Edge {
     pub from: usize,
     pub to: usize,
-    pub weight: u32,
+    pub weight: Weight,
+    #[serde(default, skip_serializing_if = "Attributes::is_empty")]
+    pub attributes: Attributes,
 }
 
-impl Edge {
-    fn has(&self, from: usize, to: usize) -> bool {
-        (self.from == from && self.to == to) || (self.from == to && self.to == from)
+/// Nodes and edges are kept in a petgraph adjacency list, indexed by the node
+/// code. Undirected edges are stored once, in the direction they were added,
+/// and walked both ways. Serialized as the plain lists of `GraphData`
+#[derive(Debug, Clone)]
+pub struct Graph {
+    is_weighted: bool,
+    is_directed: bool,
+    graph: StableDiGraph<Node, Edge>,
+    indices: HashMap<usize, NodeIndex>,
+    /// Changed since it was loaded or last saved, see `mark_clean`
+    dirty: bool,
+}
+
+/// Layout of the saved JSON
+#[derive(Deserialize)]
+struct GraphData {
+    is_weighted: bool,
+    #[serde(default)]
+    is_directed: bool,
+    edges: Vec<Edge>,
+    nodes: Vec<Node>,
+}
+
+#[derive(Serialize)]
+struct GraphDataRef<'a> {
+    is_weighted: bool,
+    is_directed: bool,
+    edges: Vec<&'a Edge>,
+    nodes: Vec<&'a Node>,
+}
+
+impl Serialize for Graph {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        GraphDataRef {
+            is_weighted: self.is_weighted,
+            is_directed: self.is_directed,
+            edges: self.edges().collect(),
+            nodes: self.nodes().collect(),
+        }
+        .serialize(serializer)
     }
 }
 
-#[derive(Debug, Serialize, Deserialize, Clone)]
-pub struct Graph {
-    pub is_weighted: bool,
-    pub size: usize,
-    pub edges: Vec<Edge>,
-    pub nodes: Vec<Node>,
+impl<'de> Deserialize<'de> for Graph {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let data = GraphData::deserialize(deserializer)?;
+        let mut graph = Graph::new(data.is_weighted, data.is_directed);
+
+        for node in data.nodes {
+            graph.add_node(node).map_err(de::Error::custom)?;
+        }
+
+        // Repeated edges were never rejected when loading, only their ends
+        // have to exist
+        for edge in data.edges {
+            graph.insert_edge(edge).map_err(de::Error::custom)?;
+        }
+
+        graph.mark_clean();
+
+        Ok(graph)
+    }
 }
 
 impl Graph {
+    pub fn new(is_weighted: bool, is_directed: bool) -> Graph {
+        Graph {
+            is_weighted,
+            is_directed,
+            graph: StableDiGraph::new(),
+            indices: HashMap::new(),
+            dirty: false,
+        }
+    }
+
+    /// Reads a graph saved in any version of the JSON format, older versions
+    /// are migrated to the current one
+    pub fn from_json(data: &str) -> Result<Graph, GraphError> {
+        let document: serde_json::Value = serde_json::from_str(data)?;
+        let version = schema::version_of(&document)?;
+
+        // Parsing the text again keeps the line and column in the errors
+        let graph: Graph = if version == schema::CURRENT_VERSION {
+            serde_json::from_str(data)?
+        } else {
+            serde_json::from_value(schema::migrate(document, version)?)?
+        };
+
+        Ok(graph)
+    }
+
+    /// Pretty-printed JSON in the current version of the format
+    pub fn to_json(&self) -> String {
+        #[derive(Serialize)]
+        struct Versioned<'a> {
+            version: u64,
+            #[serde(flatten)]
+            graph: &'a Graph,
+        }
+
+        let versioned = Versioned {
+            version: schema::CURRENT_VERSION,
+            graph: self,
+        };
+
+        let mut data = Vec::new();
+        let formatter = serde_json::ser::PrettyFormatter::with_indent(b"    ");
+        let mut serializer = serde_json::Serializer::with_formatter(&mut data, formatter);
+
+        versioned.serialize(&mut serializer).unwrap();
+        data.push(b'\n');
+
+        String::from_utf8(data).unwrap()
+    }
+
+    pub fn load(path: &Path) -> Result<Graph, GraphError> {
+        let data = fs::read_to_string(path)?;
+
+        Self::from_json(&data)
+    }
+
+    pub fn save(&self, path: &Path) -> Result<(), GraphError> {
+        fs::write(path, self.to_json())?;
+
+        Ok(())
+    }
+
+    pub fn is_weighted(&self) -> bool {
+        self.is_weighted
+    }
+
+    pub fn is_directed(&self) -> bool {
+        self.is_directed
+    }
+
+    /// Nodes in index order, which is the order they were added unless a
+    /// removed node left its slot to a new one
+    pub fn nodes(&self) -> impl Iterator<Item = &Node> {
+        self.graph.node_weights()
+    }
+
+    /// Edges sorted by their ends. Their indices aren't kept when an edge is
+    /// removed and added back, as undoing does, so they don't give a stable
+    /// order to list and save the edges in
+    pub fn edges(&self) -> impl Iterator<Item = &Edge> {
+        // Sorting collects every edge, checks that don't depend on the order
+        // walk `self.graph.edge_weights()` instead
+        let mut edges: Vec<&Edge> = self.graph.edge_weights().collect();
+        edges.sort_by_key(|edge| (edge.from, edge.to));
+
+        edges.into_iter()
+    }
+
+    /// Whether the graph changed since it was loaded from JSON or last
+    /// saved. Graphs built node by node, like the imported ones, are dirty
+    /// until they're saved
+    pub fn is_dirty(&self) -> bool {
+        self.dirty
+    }
+
+    /// Marks the graph as saved, `save` doesn't do it since the graph may be
+    /// written somewhere else than the file it was loaded from
+    pub fn mark_clean(&mut self) {
+        self.dirty = false;
+    }
+
+    /// Marks the graph as changed, e.g. when it was restored from a copy
+    /// that was never saved
+    pub fn mark_dirty(&mut self) {
+        self.dirty = true;
+    }
+
     pub fn make_weighted(&mut self) {
         self.is_weighted = true;
+        self.dirty = true;
+    }
+
+    /// Only changes the flag, the edge weights are kept
+    pub fn set_weighted(&mut self, is_weighted: bool) {
+        self.is_weighted = is_weighted;
+        self.dirty = true;
+    }
+
+    pub fn size(&self) -> usize {
+        self.graph.node_count()
     }
 
     pub fn find_by_code(&self, code: usize) -> Option<&Node> {
-        self.nodes.iter().find(|node| node.code == code)
+        self.indices.get(&code).map(|&index| &self.graph[index])
+    }
+
+    fn index(&self, code: usize) -> Result<NodeIndex, GraphError> {
+        self.indices
+            .get(&code)
+            .copied()
+            .ok_or(GraphError::NodeNotFound(code))
+    }
+
+    fn ensure_code(&self, code: usize) -> Result<(), GraphError> {
+        self.index(code).map(|_| ())
+    }
+
+    /// Looks up a node reached through the graph edges, they always point to
+    /// existing nodes since it's checked by `add_edge` and `from_json`
+    fn node(&self, code: usize) -> &Node {
+        self.find_by_code(code)
+            .expect("edges must point to existing nodes")
+    }
+
+    /// Index of the edge from `from` to `to`, in any direction when the
+    /// graph is undirected
+    fn edge_index(&self, from: usize, to: usize) -> Option<EdgeIndex> {
+        let (from, to) = (*self.indices.get(&from)?, *self.indices.get(&to)?);
+
+        if self.is_directed {
+            self.graph.find_edge(from, to)
+        } else {
+            self.graph
+                .find_edge_undirected(from, to)
+                .map(|(edge, _)| edge)
+        }
     }
 
     pub fn find_edge_by_from_to(&self, from: usize, to: usize) -> Option<&Edge> {
-        self.edges.iter().find(|edge| edge.has(from, to))
+        self.edge_index(from, to).map(|edge| &self.graph[edge])
+    }
+
+    pub fn add_node(&mut self, node: Node) -> Result<(), GraphError> {
+        if self.indices.contains_key(&node.code) {
+            return Err(GraphError::NodeAlreadyExists(node.code));
+        }
+
+        let code = node.code;
+        let index = self.graph.add_node(node);
+        self.indices.insert(code, index);
+        self.dirty = true;
+
+        Ok(())
+    }
+
+    /// Marks the graph as changed, so it's only used to change the node
+    fn node_mut(&mut self, code: usize) -> Result<&mut Node, GraphError> {
+        let index = self.index(code)?;
+        self.dirty = true;
+
+        Ok(&mut self.graph[index])
+    }
+
+    pub fn rename_node(&mut self, code: usize, name: String) -> Result<(), GraphError> {
+        self.node_mut(code)?.name = name;
+
+        Ok(())
+    }
+
+    /// Sets the attribute `key` of the node, returns the previous value
+    pub fn set_node_attribute(
+        &mut self,
+        code: usize,
+        key: String,
+        value: String,
+    ) -> Result<Option<String>, GraphError> {
+        Ok(self.node_mut(code)?.attributes.insert(key, value))
+    }
+
+    /// Removes the attribute `key` of the node, returns its value if it was set
+    pub fn remove_node_attribute(
+        &mut self,
+        code: usize,
+        key: &str,
+    ) -> Result<Option<String>, GraphError> {
+        Ok(self.node_mut(code)?.attributes.remove(key))
+    }
+
+    /// Sets the attribute `key` of the edge, returns the previous value
+    pub fn set_edge_attribute(
+        &mut self,
+        from: usize,
+        to: usize,
+        key: String,
+        value: String,
+    ) -> Result<Option<String>, GraphError> {
+        Ok(self.edge_mut(from, to)?.attributes.insert(key, value))
+    }
+
+    /// Removes the attribute `key` of the edge, returns its value if it was set
+    pub fn remove_edge_attribute(
+        &mut self,
+        from: usize,
+        to: usize,
+        key: &str,
+    ) -> Result<Option<String>, GraphError> {
+        Ok(self.edge_mut(from, to)?.attributes.remove(key))
+    }
+
+    /// Marks the graph as changed, like `node_mut`
+    fn edge_mut(&mut self, from: usize, to: usize) -> Result<&mut Edge, GraphError> {
+        let edge = self
+            .edge_index(from, to)
+            .ok_or(GraphError::EdgeNotFound { from, to })?;
+        self.dirty = true;
+
+        Ok(&mut self.graph[edge])
+    }
+
+    /// Every attribute key used by a node or an edge, sorted
+    pub fn attribute_keys(&self) -> Vec<&str> {
+        let mut keys: Vec<&str> = self
+            .nodes()
+            .flat_map(|node| node.attributes.keys())
+            .chain(
+                self.graph
+                    .edge_weights()
+                    .flat_map(|edge| edge.attributes.keys()),
+            )
+            .map(String::as_str)
+            .collect();
+
+        keys.sort_unstable();
+        keys.dedup();
+        keys
+    }
+
+    /// Nodes with the attribute `key`, when `value` is given only the ones
+    /// where it's equal, ignoring case
+    pub fn nodes_with_attribute<'a>(
+        &'a self,
+        key: &'a str,
+        value: Option<&'a str>,
+    ) -> impl Iterator<Item = &'a Node> {
+        self.nodes()
+            .filter(move |node| attribute_matches(&node.attributes, key, value))
+    }
+
+    /// Edges with the attribute `key`, when `value` is given only the ones
+    /// where it's equal, ignoring case
+    pub fn edges_with_attribute<'a>(
+        &'a self,
+        key: &'a str,
+        value: Option<&'a str>,
+    ) -> impl Iterator<Item = &'a Edge> {
+        self.edges()
+            .filter(move |edge| attribute_matches(&edge.attributes, key, value))
+    }
+
+    /// Removes the node and all of its incident edges, which are returned with it
+    pub fn remove_node(&mut self, code: usize) -> Result<(Node, Vec<Edge>), GraphError> {
+        let index = self.index(code)?;
+
+        let mut incident: Vec<EdgeIndex> = self
+            .graph
+            .edges_directed(index, Outgoing)
+            .chain(self.graph.edges_directed(index, Incoming))
+            .map(|edge| edge.id())
+            .collect();
+
+        // Buckles are both outgoing and incoming
+        incident.sort_unstable();
+        incident.dedup();
+
+        let removed_edges = incident
+            .into_iter()
+            .filter_map(|edge| self.graph.remove_edge(edge))
+            .collect();
+
+        let node = self.graph.remove_node(index).unwrap();
+        self.indices.remove(&code);
+        self.dirty = true;
+
+        Ok((node, removed_edges))
     }
 
     pub fn add_edge(&mut self, edge: Edge) -> Result<(), GraphError> {
-        match self.edges.iter().find(|e| e.has(edge.from, edge.to)) {
-            Some(_) => Err(GraphError::EdgeAlreadyExists),
-            None => {
-                self.edges.push(edge);
-                Ok(())
-            }
+        self.ensure_code(edge.from)?;
+        self.ensure_code(edge.to)?;
+
+        if self.edge_index(edge.from, edge.to).is_some() {
+            return Err(GraphError::EdgeAlreadyExists {
+                from: edge.from,
+                to: edge.to,
+            });
         }
+
+        self.insert_edge(edge)
+    }
+
+    /// Adds the edge without checking if it already exists, for edges that
+    /// were in the graph before, like the repeated ones of old files
+    pub(crate) fn insert_edge(&mut self, edge: Edge) -> Result<(), GraphError> {
+        let (from, to) = (self.index(edge.from)?, self.index(edge.to)?);
+        self.graph.add_edge(from, to, edge);
+        self.dirty = true;
+
+        Ok(())
     }
 
     pub fn is_complete(&self) -> bool {
-        let codes = self.nodes.iter().map(|n| n.code);
-        let edges: Vec<_> = self
-            .edges
-            .iter()
-            .flat_map(|e| [[e.from, e.to], [e.to, e.from]])
+        let others = self.size().saturating_sub(1);
+
+        self.nodes().all(|node| {
+            let neighbors: HashSet<usize> = self
+                .find_connected_nodes(node)
+                .into_iter()
+                .filter(|&code| code != node.code)
+                .collect();
+
+            neighbors.len() == others
+        })
+    }
+
+    pub fn is_adjacent(&self, node1: &Node, node2: &Node) -> bool {
+        self.edge_index(node1.code, node2.code).is_some()
+    }
+
+    pub fn has_buckle(&self, node: &Node) -> bool {
+        self.edge_index(node.code, node.code).is_some()
+    }
+
+    fn get_by_codes(&self, codes: &[usize]) -> Vec<&Node> {
+        codes.iter().map(|&code| self.node(code)).collect()
+    }
+
+    fn find_connected_nodes(&self, node: &Node) -> Vec<usize> {
+        self.adjacent(node.code, !self.is_directed)
+    }
+
+    /// Same as `find_connected_nodes`, but ignoring the edges direction
+    fn find_neighbors(&self, node: &Node) -> Vec<usize> {
+        self.adjacent(node.code, true)
+    }
+
+    /// Codes at the other end of the edges leaving the node, and of the ones
+    /// arriving at it when `incoming` is set. Sorted by edge index, so the
+    /// searches walk the edges in the order they were added
+    fn adjacent(&self, code: usize, incoming: bool) -> Vec<usize> {
+        let index = self.indices[&code];
+
+        let mut edges: Vec<(EdgeIndex, usize)> = self
+            .graph
+            .edges_directed(index, Outgoing)
+            .map(|edge| (edge.id(), edge.weight().to))
             .collect();
 
-        for code1 in codes.clone() {
-            let codes_without_current = codes.clone().filter(|c| *c != code1);
+        if incoming {
+            edges.extend(
+                self.graph
+                    .edges_directed(index, Incoming)
+                    // Buckles were already found as outgoing
+                    .filter(|edge| edge.weight().from != edge.weight().to)
+                    .map(|edge| (edge.id(), edge.weight().from)),
+            );
+        }
 
-            for code2 in codes_without_current {
-                if !edges.contains(&[code1, code2]) {
-                    return false;
+        edges.sort_unstable_by_key(|(edge, _)| *edge);
+        edges.into_iter().map(|(_, code)| code).collect()
+    }
+
+    pub fn get_path(
+        &self,
+        start_node: &Node,
+        end_node: &Node,
+        mode: PathMode,
+    ) -> Result<Vec<&Node>, GraphError> {
+        self.ensure_code(start_node.code)?;
+        self.ensure_code(end_node.code)?;
+
+        // Each entry holds the node code and the one it was reached from
+        let mut queue = VecDeque::from([(start_node.code, None)]);
+        let mut previous: HashMap<usize, Option<usize>> = HashMap::new();
+
+        loop {
+            let entry = match mode {
+                PathMode::Depth => queue.pop_back(),
+                PathMode::Breadth => queue.pop_front(),
+            };
+
+            let Some((current_code, parent)) = entry else {
+                return Err(GraphError::NoPath {
+                    from: start_node.code,
+                    to: end_node.code,
+                });
+            };
+
+            if previous.contains_key(&current_code) {
+                continue;
+            }
+
+            previous.insert(current_code, parent);
+
+            if current_code == end_node.code {
+                let mut path = vec![current_code];
+
+                while let Some(&Some(code)) = previous.get(path.last().unwrap()) {
+                    path.push(code);
+                }
+
+                path.reverse();
+
+                return Ok(self.get_by_codes(&path));
+            }
+
+            let current_node = self.node(current_code);
+
+            for code in self.find_connected_nodes(current_node) {
+                if !previous.contains_key(&code) {
+                    queue.push_back((code, Some(current_code)));
                 }
             }
         }
+    }
 
-        true
+    pub fn get_all_paths(
+        &self,
+        start_node: &Node,
+        end_node: &Node,
+        max_length: Option<usize>,
+        max_weight: Option<Weight>,
+    ) -> Result<Vec<(Weight, Vec<&Node>)>, GraphError> {
+        self.ensure_code(start_node.code)?;
+        self.ensure_code(end_node.code)?;
+
+        // A negative edge further on can bring the cost back under the limit,
+        // so then the paths are only filtered once they're complete
+        let can_prune = !self.has_negative_weights();
+
+        let mut paths = Vec::new();
+        let mut stack = vec![(vec![start_node.code], Weight::ZERO)];
+
+        while let Some((path, cost)) = stack.pop() {
+            let current_code = *path.last().unwrap();
+
+            if current_code == end_node.code {
+                if max_weight.is_none_or(|max| cost <= max) {
+                    paths.push((cost, self.get_by_codes(&path)));
+                }
+
+                continue;
+            }
+
+            // Extending the path adds one more edge, which would go over the limit
+            if max_length.is_some_and(|max| path.len() > max) {
+                continue;
+            }
+
+            let current_node = self.node(current_code);
+
+            for code in self.find_connected_nodes(current_node) {
+                if path.contains(&code) {
+                    continue;
+                }
+
+                let edge = self.find_edge_by_from_to(current_code, code).unwrap();
+                let new_cost = cost + edge.weight;
+
+                if can_prune && max_weight.is_some_and(|max| new_cost > max) {
+                    continue;
+                }
+
+                let mut new_path = path.clone();
+                new_path.push(code);
+                stack.push((new_path, new_cost));
+            }
+        }
+
+        paths.sort_by_key(|(cost, path)| (*cost, path.len()));
+
+        Ok(paths)
     }
 
-    pub fn is_adjacent(&self, node1: &Node, node2: &Node) -> bool {
-        self.edges
-            .iter()
-            .find(|&edge| edge.has(node1.code, node2.code))
-            .is_some()
+    /// Checks the cycle shape only, the first and last codes must be equal
+    /// and there can't be repeated codes between them
+    pub fn validate_cycle(codes: &[usize]) -> Result<(), GraphError> {
+        if codes.len() <= 2 {
+            return Err(GraphError::InvalidCycle {
+                position: codes.len().saturating_sub(1),
+                kind: CycleErrorKind::TooShort,
+            });
+        }
+
+        if codes.first() != codes.last() {
+            return Err(GraphError::InvalidCycle {
+                position: codes.len() - 1,
+                kind: CycleErrorKind::NotClosed,
+            });
+        }
+
+        let mut seen = HashSet::new();
+
+        // The last element closes the cycle, so it's expected to be repeated
+        for (position, &code) in codes[..codes.len() - 1].iter().enumerate() {
+            if !seen.insert(code) {
+                return Err(GraphError::InvalidCycle {
+                    position,
+                    kind: CycleErrorKind::RepeatedNode(code),
+                });
+            }
+        }
+
+        Ok(())
     }
 
-    pub fn has_buckle(&self, node: &Node) -> bool {
-        self.edges
-            .iter()
-            .find(|&edge| edge.has(node.code, node.code))
-            .is_some()
+    pub fn get_cycle(&self, codes: &[usize]) -> Result<Vec<&Node>, GraphError> {
+        Self::validate_cycle(codes)?;
+
+        for &code in codes {
+            self.ensure_code(code)?;
+        }
+
+        for (position, pair) in codes.windows(2).enumerate() {
+            if self.edge_index(pair[0], pair[1]).is_none() {
+                return Err(GraphError::InvalidCycle {
+                    position: position + 1,
+                    kind: CycleErrorKind::MissingEdge {
+                        from: pair[0],
+                        to: pair[1],
+                    },
+                });
+            }
+        }
+
+        Ok(self.get_by_codes(codes))
     }
 
-    fn get_by_codes(&self, codes: &Vec<usize>) -> Vec<&Node> {
-        codes
-            .iter()
-            .map(|c| self.nodes.iter().find(|n| n.code == *c).unwrap())
-            .collect()
+    pub fn is_acyclic(&self) -> bool {
+        let mut finished = HashSet::new();
+        let mut on_stack = HashSet::new();
+
+        for node in self.nodes() {
+            if finished.contains(&node.code) {
+                continue;
+            }
+
+            // Each entry holds the node code, its parent and the neighbors left to visit
+            let mut stack = vec![(node.code, None, self.find_connected_nodes(node))];
+            on_stack.insert(node.code);
+
+            while let Some((code, parent, neighbors)) = stack.last_mut() {
+                let code = *code;
+                let parent = *parent;
+
+                let Some(neighbor) = neighbors.pop() else {
+                    on_stack.remove(&code);
+                    finished.insert(code);
+                    stack.pop();
+                    continue;
+                };
+
+                // Buckles are not cycles, and an undirected edge can't be walked back
+                if neighbor == code || (!self.is_directed && Some(neighbor) == parent) {
+                    continue;
+                }
+
+                if on_stack.contains(&neighbor) {
+                    return false;
+                }
+
+                if finished.contains(&neighbor) {
+                    continue;
+                }
+
+                let neighbor_node = self.node(neighbor);
+                on_stack.insert(neighbor);
+                stack.push((
+                    neighbor,
+                    Some(code),
+                    self.find_connected_nodes(neighbor_node),
+                ));
+            }
+        }
+
+        true
     }
 
-    fn find_connected_nodes(&self, node: &Node) -> Vec<usize> {
-        self.edges
-            .iter()
-            .filter_map(|edge| {
-                if edge.from == node.code {
-                    return Some(edge.to);
-                } else if edge.to == node.code {
-                    return Some(edge.from);
+    pub fn find_cycles(&self) -> Vec<Vec<&Node>> {
+        let positions: HashMap<usize, usize> = self
+            .nodes()
+            .enumerate()
+            .map(|(i, node)| (node.code, i))
+            .collect();
+
+        // An undirected cycle needs at least three vertices, otherwise the same
+        // edge would be walked twice
+        let min_length = if self.is_directed { 2 } else { 3 };
+        let mut cycles = Vec::new();
+
+        for (start_position, start_node) in self.nodes().enumerate() {
+            let mut stack = vec![vec![start_node.code]];
+
+            while let Some(path) = stack.pop() {
+                let current_code = *path.last().unwrap();
+                let current_node = self.node(current_code);
+
+                for code in self.find_connected_nodes(current_node) {
+                    if code == start_node.code {
+                        // Buckles and, in undirected graphs, walking an edge back are
+                        // too short to be cycles
+                        if path.len() < min_length {
+                            continue;
+                        }
+
+                        // Undirected cycles are found once in each direction, keep only one
+                        let is_duplicate =
+                            !self.is_directed && positions[&path[1]] > positions[&current_code];
+
+                        if !is_duplicate {
+                            let mut cycle = path.clone();
+                            cycle.push(code);
+                            cycles.push(self.get_by_codes(&cycle));
+                        }
+
+                        continue;
+                    }
+
+                    // Each cycle is only walked from its vertex that comes first in the node list
+                    if positions[&code] > start_position && !path.contains(&code) {
+                        let mut new_path = path.clone();
+                        new_path.push(code);
+                        stack.push(new_path);
+                    }
                 }
+            }
+        }
+
+        cycles.sort_by_key(|cycle| cycle.len());
+
+        cycles
+    }
+
+    pub fn cycles_through(&self, node: &Node) -> Result<Vec<Vec<&Node>>, GraphError> {
+        self.ensure_code(node.code)?;
+
+        let cycles = self
+            .find_cycles()
+            .into_iter()
+            .filter_map(|mut cycle| {
+                let position = cycle.iter().position(|n| n.code == node.code)?;
 
-                None
+                // Rotate the cycle so it starts and ends on the given node
+                cycle.pop();
+                cycle.rotate_left(position);
+                cycle.push(cycle[0]);
+
+                Some(cycle)
             })
-            .collect()
+            .collect();
+
+        Ok(cycles)
     }
 
-    pub fn get_path(&self, start_node: &Node, end_node: &Node) -> Option<Vec<&Node>> {
-        let mut queue = Vec::new();
-        let mut visited = Vec::new();
+    pub fn cycle_weight(&self, cycle: &[&Node]) -> Result<Weight, GraphError> {
+        cycle
+            .windows(2)
+            .map(|pair| {
+                let (from, to) = (pair[0].code, pair[1].code);
 
-        queue.push(vec![start_node.code]);
-        visited.push(start_node.code);
+                self.find_edge_by_from_to(from, to)
+                    .map(|edge| edge.weight)
+                    .ok_or(GraphError::EdgeNotFound { from, to })
+            })
+            .sum()
+    }
 
-        while let Some(path) = queue.pop() {
-            let current_code = path.last().unwrap();
-            visited.push(*current_code);
+    /// Sets the weight of the edge, which has to be a finite number other
+    /// than 0, the same rule the weights read from the user follow
+    pub fn add_weight(&mut self, from: usize, to: usize, weight: Weight) -> Result<(), GraphError> {
+        if weight.is_zero() || !weight.as_f64().is_finite() {
+            return Err(GraphError::InvalidWeight(weight.to_string()));
+        }
 
-            if *current_code == end_node.code {
-                let nodes = self.get_by_codes(&path);
-                return Some(nodes);
+        self.edge_mut(from, to)?.weight = weight;
+
+        Ok(())
+    }
+
+    /// Removes the edge and returns it
+    pub fn remove_edge(&mut self, from: usize, to: usize) -> Result<Edge, GraphError> {
+        let edge = self
+            .edge_index(from, to)
+            .ok_or(GraphError::EdgeNotFound { from, to })?;
+        self.dirty = true;
+
+        Ok(self.graph.remove_edge(edge).unwrap())
+    }
+
+    pub fn has_negative_weights(&self) -> bool {
+        self.graph
+            .edge_weights()
+            .any(|edge| edge.weight.is_negative())
+    }
+
+    /// Cheapest path and its cost, using Dijkstra's algorithm, or
+    /// Bellman-Ford's when there are negative weights
+    pub fn calculate_path(
+        &self,
+        start_node: &Node,
+        end_node: &Node,
+    ) -> Result<(Weight, Vec<&Node>), GraphError> {
+        if self.has_negative_weights() {
+            return self.bellman_ford(start_node, end_node);
+        }
+
+        self.ensure_code(start_node.code)?;
+        self.ensure_code(end_node.code)?;
+
+        let mut distances = HashMap::new();
+        let mut previous = HashMap::new();
+        let mut queue = BinaryHeap::new();
+
+        distances.insert(start_node.code, Weight::ZERO);
+        queue.push(Reverse((Weight::ZERO, start_node.code)));
+
+        while let Some(Reverse((distance, current_code))) = queue.pop() {
+            if current_code == end_node.code {
+                let mut path = vec![current_code];
+
+                while let Some(code) = previous.get(path.last().unwrap()) {
+                    path.push(*code);
+                }
+
+                path.reverse();
+
+                return Ok((distance, self.get_by_codes(&path)));
             }
 
-            let current_node = self.find_by_code(*current_code).unwrap();
-            let current_node_edges = self.find_connected_nodes(&current_node);
+            // Stale entry, a shorter distance to this node was already found
+            if distance > distances[&current_code] {
+                continue;
+            }
+
+            let current_node = self.node(current_code);
+
+            for code in self.find_connected_nodes(current_node) {
+                let edge = self.find_edge_by_from_to(current_code, code).unwrap();
+                let new_distance = distance + edge.weight;
 
-            for code in current_node_edges {
-                if !visited.contains(&code) {
-                    let mut new_path = path.clone();
-                    new_path.push(code);
-                    queue.push(new_path);
+                if distances.get(&code).is_none_or(|&d| new_distance < d) {
+                    distances.insert(code, new_distance);
+                    previous.insert(code, current_code);
+                    queue.push(Reverse((new_distance, code)));
                 }
             }
         }
 
-        return None;
+        Err(GraphError::NoPath {
+            from: start_node.code,
+            to: end_node.code,
+        })
     }
 
-    pub fn is_cycle(codes: &Vec<usize>) -> bool {
-        if codes.len() <= 2 || codes.first().unwrap() != codes.last().unwrap() {
-            return false;
+    /// Cheapest path and its cost using Bellman-Ford's algorithm, which allows
+    /// negative weights. Fails with `NegativeCycle` when a cycle with negative
+    /// cost can be reached from the start, in undirected graphs that's any
+    /// negative edge, since it can be walked back and forth
+    pub fn bellman_ford(
+        &self,
+        start_node: &Node,
+        end_node: &Node,
+    ) -> Result<(Weight, Vec<&Node>), GraphError> {
+        self.ensure_code(start_node.code)?;
+        self.ensure_code(end_node.code)?;
+
+        // Undirected edges are relaxed both ways
+        let arcs: Vec<(usize, usize, Weight)> = self
+            .graph
+            .edge_weights()
+            .flat_map(|edge| {
+                let back = (!self.is_directed && edge.from != edge.to).then_some((
+                    edge.to,
+                    edge.from,
+                    edge.weight,
+                ));
+
+                std::iter::once((edge.from, edge.to, edge.weight)).chain(back)
+            })
+            .collect();
+
+        let mut distances = HashMap::from([(start_node.code, Weight::ZERO)]);
+        let mut previous = HashMap::new();
+
+        // A shortest path has at most size - 1 edges, an arc that still
+        // relaxes after that many rounds is part of, or reached by, a
+        // negative cycle
+        for round in 0..self.size() {
+            let mut relaxed = None;
+
+            for &(from, to, weight) in &arcs {
+                let Some(&distance) = distances.get(&from) else {
+                    continue;
+                };
+
+                let new_distance = distance + weight;
+
+                if distances.get(&to).is_none_or(|&d| new_distance < d) {
+                    distances.insert(to, new_distance);
+                    previous.insert(to, from);
+                    relaxed = Some(to);
+                }
+            }
+
+            match relaxed {
+                None => break,
+                Some(code) if round == self.size() - 1 => {
+                    return Err(GraphError::NegativeCycle(negative_cycle(&previous, code)));
+                }
+                Some(_) => {}
+            }
         }
 
-        let mut unique_codes = codes.clone();
-        unique_codes.sort_unstable();
-        unique_codes.dedup();
+        let Some(&distance) = distances.get(&end_node.code) else {
+            return Err(GraphError::NoPath {
+                from: start_node.code,
+                to: end_node.code,
+            });
+        };
+
+        let mut path = vec![end_node.code];
 
-        // The +1 here is because the first and last elements must be equal,
-        // so it's removed on dedup
-        if codes.len() != unique_codes.len() + 1 {
-            return false;
+        while let Some(code) = previous.get(path.last().unwrap()) {
+            path.push(*code);
         }
 
-        return true;
+        path.reverse();
+
+        Ok((distance, self.get_by_codes(&path)))
     }
 
-    pub fn get_cycle(&self, codes: &Vec<usize>) -> Option<Vec<&Node>> {
-        if !Self::is_cycle(&codes) {
-            return None;
+    /// Builds the minimum spanning tree (or forest, when the graph isn't connected)
+    /// using Kruskal's algorithm, returns the tree and its total cost
+    pub fn minimum_spanning_tree(&self) -> Result<(Graph, Weight), GraphError> {
+        if self.is_directed {
+            return Err(GraphError::DirectedGraph);
         }
 
-        let mut cycle = Vec::new();
-        let mut codes_iter = codes.iter();
+        let mut parents: HashMap<usize, usize> =
+            self.nodes().map(|node| (node.code, node.code)).collect();
 
-        let first_node = match self.find_by_code(*codes_iter.next().unwrap()) {
-            Some(node) => node,
-            None => return None,
-        };
+        let mut edges: Vec<&Edge> = self.edges().collect();
+        edges.sort_by_key(|edge| edge.weight);
 
-        cycle.push(first_node);
+        let mut tree = Graph::new(self.is_weighted, false);
 
-        let mut current_node = first_node;
+        for node in self.nodes() {
+            tree.add_node(node.clone())?;
+        }
+
+        let mut cost = Weight::ZERO;
 
-        while let Some(code) = codes_iter.next() {
-            let current_node_edges = self.find_connected_nodes(current_node);
+        for edge in edges {
+            let from_root = find_root(&mut parents, edge.from);
+            let to_root = find_root(&mut parents, edge.to);
 
-            if !current_node_edges.iter().find(|c| *c == code).is_some() {
-                return None;
+            // Both ends are already connected, the edge would close a cycle
+            if from_root == to_root {
+                continue;
             }
 
-            current_node = self.find_by_code(*code).unwrap();
-            cycle.push(current_node);
+            parents.insert(from_root, to_root);
+            cost += edge.weight;
+            tree.insert_edge(edge.clone())?;
         }
 
-        Some(cycle)
+        Ok((tree, cost))
     }
 
-    pub fn add_weight(edge: &mut Edge, weight: u32) {
-        edge.weight = weight;
+    /// Subgraph with the nodes of `path` and the edges between consecutive
+    /// nodes, used to highlight a path or cycle in the exports
+    pub fn path_subgraph(&self, path: &[&Node]) -> Graph {
+        let mut subgraph = Graph::new(self.is_weighted, self.is_directed);
+
+        for node in path {
+            // Cycles start and end with the same node
+            if subgraph.find_by_code(node.code).is_none() {
+                subgraph.add_node((*node).clone()).unwrap();
+            }
+        }
+
+        for pair in path.windows(2) {
+            if let Some(edge) = self.find_edge_by_from_to(pair[0].code, pair[1].code) {
+                subgraph.insert_edge(edge.clone()).unwrap();
+            }
+        }
+
+        subgraph
     }
 
-    pub fn remove_edge(&mut self, from: usize, to: usize) -> Result<(), GraphError> {
-        let edge = self.find_edge_by_from_to(from, to);
+    /// Groups the node codes by connected component, for directed graphs the
+    /// edges direction is ignored (weakly connected components)
+    pub fn connected_components(&self) -> Vec<Vec<usize>> {
+        let mut visited = HashSet::new();
+        let mut components = Vec::new();
+
+        for node in self.nodes() {
+            if !visited.insert(node.code) {
+                continue;
+            }
+
+            let mut component = Vec::new();
+            let mut queue = VecDeque::from([node.code]);
+
+            while let Some(code) = queue.pop_front() {
+                component.push(code);
+
+                let current_node = self.node(code);
+
+                for neighbor in self.find_neighbors(current_node) {
+                    if visited.insert(neighbor) {
+                        queue.push_back(neighbor);
+                    }
+                }
+            }
 
-        if edge.is_none() {
-            return Err(GraphError::EdgeDontExists);
+            component.sort_unstable();
+            components.push(component);
         }
 
-        let edge = edge.unwrap().clone();
+        components
+    }
 
-        self.edges.retain(|e| e != &edge);
+    pub fn is_connected(&self) -> bool {
+        self.connected_components().len() <= 1
+    }
 
-        Ok(())
+    pub fn bridges(&self) -> Vec<&Edge> {
+        self.find_bridges_and_articulation_points().0
+    }
+
+    pub fn articulation_points(&self) -> Vec<&Node> {
+        self.find_bridges_and_articulation_points().1
     }
 
-    pub fn calculate_path(&self, start_node: &Node, end_node: &Node) -> Option<u32> {
-        let mut path_sum = 0;
-        let path = self.get_path(start_node, end_node)?;
-        let mut iter = path.iter().peekable();
+    pub fn is_bridge(&self, from: usize, to: usize) -> bool {
+        self.bridges().iter().any(|edge| {
+            (edge.from == from && edge.to == to) || (edge.from == to && edge.to == from)
+        })
+    }
+
+    /// Tarjan's algorithm, for directed graphs the edges direction is ignored
+    fn find_bridges_and_articulation_points(&self) -> (Vec<&Edge>, Vec<&Node>) {
+        let mut discovery = HashMap::new();
+        let mut low = HashMap::new();
+        let mut timer = 0;
 
-        while let Some(node_from) = iter.next() {
-            let node_to = iter.peek();
+        let mut bridges = Vec::new();
+        let mut points = Vec::new();
 
-            if node_to.is_none() {
-                break;
+        for root in self.nodes() {
+            if discovery.contains_key(&root.code) {
+                continue;
             }
 
-            let edge = self
-                .find_edge_by_from_to(node_from.code, node_to.unwrap().code)
-                .unwrap();
+            discovery.insert(root.code, timer);
+            low.insert(root.code, timer);
+            timer += 1;
+
+            let mut root_children = 0;
+
+            // Each entry holds the node code, its parent, the neighbors left to visit
+            // and whether the edge back to the parent was already skipped
+            let mut stack = vec![(root.code, None, self.find_neighbors(root), false)];
+
+            while let Some((code, parent, neighbors, parent_skipped)) = stack.last_mut() {
+                let code = *code;
+                let parent = *parent;
+
+                let Some(neighbor) = neighbors.pop() else {
+                    stack.pop();
+
+                    let Some(parent) = parent else {
+                        continue;
+                    };
+
+                    let child_low = low[&code];
+                    let parent_discovery = discovery[&parent];
+
+                    if child_low < low[&parent] {
+                        low.insert(parent, child_low);
+                    }
+
+                    if child_low > parent_discovery {
+                        let (edge, _) = self
+                            .graph
+                            .find_edge_undirected(self.indices[&parent], self.indices[&code])
+                            .unwrap();
+                        bridges.push(&self.graph[edge]);
+                    }
+
+                    if parent != root.code && child_low >= parent_discovery {
+                        points.push(parent);
+                    }
+
+                    continue;
+                };
+
+                if neighbor == code {
+                    continue;
+                }
+
+                // Only the edge used to reach this node is ignored, a second one
+                // between the same nodes (e.g. both directions) still counts
+                if Some(neighbor) == parent && !*parent_skipped {
+                    *parent_skipped = true;
+                    continue;
+                }
+
+                if let Some(&neighbor_discovery) = discovery.get(&neighbor) {
+                    if neighbor_discovery < low[&code] {
+                        low.insert(code, neighbor_discovery);
+                    }
 
-            path_sum += edge.weight;
+                    continue;
+                }
+
+                if code == root.code {
+                    root_children += 1;
+                }
+
+                discovery.insert(neighbor, timer);
+                low.insert(neighbor, timer);
+                timer += 1;
+
+                let neighbor_node = self.node(neighbor);
+                stack.push((
+                    neighbor,
+                    Some(code),
+                    self.find_neighbors(neighbor_node),
+                    false,
+                ));
+            }
+
+            if root_children > 1 {
+                points.push(root.code);
+            }
         }
 
-        Some(path_sum)
+        points.sort_unstable();
+        points.dedup();
+
+        (bridges, self.get_by_codes(&points))
     }
 
     pub fn is_subgraph(&self, subgraph: &Graph) -> bool {
-        let subgraph_node_codes: Vec<usize> = subgraph.nodes.iter().map(|el| el.code).collect();
+        if self.is_directed != subgraph.is_directed {
+            return false;
+        }
+
+        let subgraph_node_codes: HashSet<usize> = subgraph.indices.keys().copied().collect();
 
-        for sub_node in &subgraph.nodes {
-            if !self.nodes.contains(&sub_node) {
+        for sub_node in subgraph.nodes() {
+            // Attributes are metadata, only the node itself has to match
+            let has_node = self.find_by_code(sub_node.code).is_some_and(|node| {
+                node.name == sub_node.name && node.local_type == sub_node.local_type
+            });
+
+            if !has_node {
                 return false;
             }
 
@@ -242,67 +1197,480 @@ impl Graph {
 
             if sub_node_edges.is_empty() {
                 for edge in &node_edges {
-                    if subgraph_node_codes.contains(&edge) {
+                    if subgraph_node_codes.contains(edge) {
                         return false;
                     }
                 }
             }
 
             for sub_node_edge in &sub_node_edges {
-                if !node_edges.contains(&sub_node_edge) {
+                if !node_edges.contains(sub_node_edge) {
                     return false;
                 }
             }
         }
 
-        return true;
+        true
     }
 }
 
 impl Display for Graph {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        let mut string = "** Grafo **\n".blue().bold().to_string();
-        let mut iter = self.nodes.iter().peekable();
-
-        while let Some(node) = iter.next() {
+        let mut string = "** Grafo **\n".to_string();
+        for node in self.nodes() {
             string = format!("{string}{node}\n");
-
-            if iter.peek().is_some() {
-                string = format!("{string}");
-            }
         }
 
-        if self.edges.is_empty() {
+        if self.graph.edge_count() == 0 {
             return write!(f, "{string}");
         }
 
-        string.push_str(&"\n** Arestas **".blue().bold().to_string());
-        string = format!("{string}\n{}", format_edges(self.is_weighted, &self.edges));
+        string.push_str("\n** Arestas **");
+        string = format!(
+            "{string}\n{}",
+            format_edges(self.is_weighted, self.is_directed, self.edges())
+        );
 
         write!(f, "{string}")
     }
 }
 
-fn format_edges(weighted: bool, edges: &Vec<Edge>) -> String {
+/// Cycle found by Bellman-Ford from a node still relaxed in the last round,
+/// walking back its predecessors until a node repeats. The codes follow the
+/// edges direction and the first one is repeated at the end
+fn negative_cycle(previous: &HashMap<usize, usize>, code: usize) -> Vec<usize> {
+    let mut seen = HashSet::new();
+    let mut current = code;
+
+    // The node may only be reached by the cycle, not be part of it
+    while seen.insert(current) {
+        current = previous[&current];
+    }
+
+    let mut cycle = vec![current];
+    let mut node = previous[&current];
+
+    while node != current {
+        cycle.push(node);
+        node = previous[&node];
+    }
+
+    cycle.push(current);
+    cycle.reverse();
+    cycle
+}
+
+fn find_root(parents: &mut HashMap<usize, usize>, code: usize) -> usize {
+    let mut root = code;
+
+    while parents[&root] != root {
+        root = parents[&root];
+    }
+
+    // Point every node in the way directly to the root, so next lookups are faster
+    let mut current = code;
+
+    while current != root {
+        let next = parents[&current];
+        parents.insert(current, root);
+        current = next;
+    }
+
+    root
+}
+
+fn attribute_matches(attributes: &Attributes, key: &str, value: Option<&str>) -> bool {
+    match (attributes.get(key), value) {
+        (Some(actual), Some(value)) => actual.to_lowercase() == value.to_lowercase(),
+        (Some(_), None) => true,
+        (None, _) => false,
+    }
+}
+
+fn format_edges<'a>(
+    weighted: bool,
+    directed: bool,
+    edges: impl Iterator<Item = &'a Edge>,
+) -> String {
     let mut string = "".to_string();
-    let mut iter = edges.iter().peekable();
+    let mut iter = edges.peekable();
+    let arrow = if directed { "->" } else { "<->" };
 
     while let Some(edge) = iter.next() {
-        string = format!(
-            "{string}{} <-> {}",
-            edge.from.to_string().cyan(),
-            edge.to.to_string().cyan()
-        );
+        string = format!("{string}{} {arrow} {}", edge.from, edge.to);
 
         if weighted {
-            string = format!("{string}  Peso = {}", edge.weight.to_string().cyan());
+            string = format!("{string}  Peso = {}", edge.weight);
+        }
+
+        if !edge.attributes.is_empty() {
+            string = format!("{string}  ({})", node::format_attributes(&edge.attributes));
         }
 
-        string = format!("{string}");
         if iter.peek().is_some() {
             string = format!("{string}\n");
         }
     }
 
-    format!("{string}")
+    string
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::fixtures::{build, campus, codes};
+
+    #[test]
+    fn find_cycles_ignores_buckles() {
+        let graph = build(false, &[1, 2, 3], &[(1, 1, 1), (1, 2, 1)]);
+
+        assert!(graph.find_cycles().is_empty());
+
+        let graph = build(
+            false,
+            &[1, 2, 3],
+            &[(1, 1, 1), (1, 2, 1), (2, 3, 1), (3, 1, 1)],
+        );
+        let cycles: Vec<Vec<usize>> = graph.find_cycles().iter().map(|c| codes(c)).collect();
+
+        assert_eq!(cycles, vec![vec![1, 2, 3, 1]]);
+        assert_eq!(graph.cycles_through(graph.node(2)).unwrap().len(), 1);
+    }
+
+    #[test]
+    fn calculate_path_finds_the_cheapest_path() {
+        let graph = build(
+            true,
+            &[1, 2, 3, 4],
+            &[(1, 2, 1), (2, 4, 5), (1, 3, 2), (3, 4, 1)],
+        );
+
+        let (weight, path) = graph.calculate_path(graph.node(1), graph.node(4)).unwrap();
+
+        assert_eq!(weight, Weight::Integer(3));
+        assert_eq!(codes(&path), vec![1, 3, 4]);
+        assert!(matches!(
+            graph.calculate_path(graph.node(4), graph.node(1)),
+            Err(GraphError::NoPath { from: 4, to: 1 })
+        ));
+    }
+
+    #[test]
+    fn breadth_first_path_has_the_fewest_edges() {
+        let graph = build(
+            false,
+            &[1, 2, 3, 4],
+            &[(1, 2, 1), (2, 3, 1), (3, 4, 1), (1, 4, 9)],
+        );
+
+        let path = graph
+            .get_path(graph.node(1), graph.node(4), PathMode::Breadth)
+            .unwrap();
+
+        assert_eq!(codes(&path), vec![1, 4]);
+
+        let path = graph
+            .get_path(graph.node(1), graph.node(4), PathMode::Depth)
+            .unwrap();
+
+        assert_eq!(path.first().unwrap().code, 1);
+        assert_eq!(path.last().unwrap().code, 4);
+    }
+
+    #[test]
+    fn get_all_paths_lists_the_simple_paths_by_cost() {
+        let graph = build(
+            false,
+            &[1, 2, 3, 4],
+            &[(1, 2, 1), (2, 4, 1), (1, 3, 5), (3, 4, 1), (2, 3, 1)],
+        );
+
+        let paths: Vec<(Weight, Vec<usize>)> = graph
+            .get_all_paths(graph.node(1), graph.node(4), None, None)
+            .unwrap()
+            .into_iter()
+            .map(|(cost, path)| (cost, codes(&path)))
+            .collect();
+
+        assert_eq!(
+            paths,
+            vec![
+                (Weight::Integer(2), vec![1, 2, 4]),
+                (Weight::Integer(3), vec![1, 2, 3, 4]),
+                (Weight::Integer(6), vec![1, 3, 4]),
+                (Weight::Integer(7), vec![1, 3, 2, 4]),
+            ]
+        );
+
+        let limited = graph
+            .get_all_paths(
+                graph.node(1),
+                graph.node(4),
+                Some(2),
+                Some(Weight::Integer(6)),
+            )
+            .unwrap();
+
+        assert_eq!(limited.len(), 2);
+    }
+
+    #[test]
+    fn is_acyclic_ignores_buckles() {
+        assert!(build(false, &[1, 2], &[(1, 1, 1), (1, 2, 1)]).is_acyclic());
+        assert!(build(true, &[1, 2], &[(1, 2, 1), (2, 2, 1)]).is_acyclic());
+        assert!(!build(true, &[1, 2], &[(1, 2, 1), (2, 1, 1)]).is_acyclic());
+        assert!(!build(false, &[1, 2, 3], &[(1, 2, 1), (2, 3, 1), (3, 1, 1)]).is_acyclic());
+    }
+
+    #[test]
+    fn minimum_spanning_tree_keeps_the_cheapest_edges() {
+        let graph = build(
+            false,
+            &[1, 2, 3, 4, 5],
+            &[(1, 2, 4), (2, 3, 1), (1, 3, 2), (3, 4, 7), (4, 4, 1)],
+        );
+
+        let (tree, cost) = graph.minimum_spanning_tree().unwrap();
+        let edges: Vec<(usize, usize)> = tree.edges().map(|edge| (edge.from, edge.to)).collect();
+
+        // 5 is isolated, so it's a forest
+        assert_eq!(cost, Weight::Integer(10));
+        assert_eq!(edges, vec![(1, 3), (2, 3), (3, 4)]);
+        assert_eq!(tree.size(), 5);
+        assert!(matches!(
+            build(true, &[1], &[]).minimum_spanning_tree(),
+            Err(GraphError::DirectedGraph)
+        ));
+    }
+
+    #[test]
+    fn connected_components_ignore_the_direction() {
+        let graph = build(true, &[1, 2, 3, 4, 5], &[(2, 1, 1), (3, 2, 1), (4, 5, 1)]);
+
+        assert_eq!(
+            graph.connected_components(),
+            vec![vec![1, 2, 3], vec![4, 5]]
+        );
+        assert!(!graph.is_connected());
+        assert!(build(false, &[1, 2], &[(1, 2, 1)]).is_connected());
+        assert!(Graph::new(false, false).is_connected());
+    }
+
+    #[test]
+    fn finds_bridges_and_articulation_points() {
+        // A triangle 1-2-3 hanging from 4, which leads to 5
+        let graph = build(
+            false,
+            &[1, 2, 3, 4, 5],
+            &[(1, 2, 1), (2, 3, 1), (3, 1, 1), (3, 4, 1), (4, 5, 1)],
+        );
+
+        let bridges: Vec<(usize, usize)> = graph
+            .bridges()
+            .iter()
+            .map(|edge| (edge.from, edge.to))
+            .collect();
+        let mut points = codes(&graph.articulation_points());
+        points.sort_unstable();
+
+        assert_eq!(bridges.len(), 2);
+        assert!(bridges.contains(&(3, 4)) && bridges.contains(&(4, 5)));
+        assert_eq!(points, vec![3, 4]);
+        assert!(graph.is_bridge(4, 3));
+        assert!(!graph.is_bridge(1, 2));
+    }
+
+    #[test]
+    fn remove_node_drops_its_edges() {
+        let mut graph = build(
+            true,
+            &[1, 2, 3],
+            &[(1, 2, 1), (2, 3, 2), (3, 1, 3), (2, 2, 4)],
+        );
+
+        let (node, edges) = graph.remove_node(2).unwrap();
+        let removed: Vec<(usize, usize)> = edges.iter().map(|edge| (edge.from, edge.to)).collect();
+        let left: Vec<(usize, usize)> = graph.edges().map(|edge| (edge.from, edge.to)).collect();
+
+        assert_eq!(node.code, 2);
+        assert_eq!(removed.len(), 3);
+        assert!([(1, 2), (2, 3), (2, 2)]
+            .iter()
+            .all(|edge| removed.contains(edge)));
+        assert_eq!(left, vec![(3, 1)]);
+        assert_eq!(graph.size(), 2);
+        assert!(graph.find_by_code(2).is_none());
+        assert!(matches!(
+            graph.remove_node(2),
+            Err(GraphError::NodeNotFound(2))
+        ));
+    }
+
+    #[test]
+    fn round_trips_the_json() {
+        for is_directed in [false, true] {
+            let graph = campus(is_directed);
+            let parsed = Graph::from_json(&graph.to_json()).unwrap();
+
+            assert_eq!(parsed.to_json(), graph.to_json());
+        }
+    }
+
+    #[test]
+    fn reads_unversioned_json() {
+        let data = r#"{"is_weighted": false, "size": 2, "nodes": [
+            {"code": 1, "name": "A", "local_type": ""},
+            {"code": 2, "name": "B", "local_type": ""}
+        ], "edges": [{"from": 1, "to": 2, "weight": 1}]}"#;
+
+        let graph = Graph::from_json(data).unwrap();
+
+        assert!(!graph.is_directed());
+        assert_eq!(graph.size(), 2);
+        assert!(graph.is_adjacent(graph.node(2), graph.node(1)));
+        assert!(graph
+            .to_json()
+            .contains(&format!("\"version\": {}", schema::CURRENT_VERSION)));
+    }
+
+    #[test]
+    fn queries_nodes_and_edges_by_attribute() {
+        let mut graph = campus(false);
+        graph
+            .set_node_attribute(2, "andar".to_string(), "Térreo".to_string())
+            .unwrap();
+        graph
+            .set_edge_attribute(2, 5, "acessível".to_string(), "sim".to_string())
+            .unwrap();
+
+        let nodes: Vec<usize> = graph
+            .nodes_with_attribute("andar", None)
+            .map(|node| node.code)
+            .collect();
+        let terreo: Vec<usize> = graph
+            .nodes_with_attribute("andar", Some("TÉRREO"))
+            .map(|node| node.code)
+            .collect();
+
+        assert_eq!(nodes, vec![1, 2]);
+        assert_eq!(terreo, vec![2]);
+        assert_eq!(
+            graph.edges_with_attribute("coberta", Some("sim")).count(),
+            1
+        );
+        assert_eq!(
+            graph.edges_with_attribute("coberta", Some("não")).count(),
+            0
+        );
+        assert_eq!(
+            graph.attribute_keys(),
+            vec!["acessível", "andar", "coberta"]
+        );
+
+        assert_eq!(
+            graph.remove_node_attribute(2, "andar").unwrap(),
+            Some("Térreo".to_string())
+        );
+        assert_eq!(graph.nodes_with_attribute("andar", None).count(), 1);
+    }
+
+    #[test]
+    fn tracks_unsaved_changes() {
+        let mut graph = Graph::from_json(&campus(false).to_json()).unwrap();
+
+        assert!(!graph.is_dirty());
+
+        // Reading doesn't change the graph
+        graph.find_cycles();
+        graph.calculate_path(graph.node(1), graph.node(5)).ok();
+        assert!(!graph.is_dirty());
+
+        graph.rename_node(1, "Sala B".to_string()).unwrap();
+        assert!(graph.is_dirty());
+
+        graph.mark_clean();
+        graph.remove_edge(1, 2).unwrap();
+        assert!(graph.is_dirty());
+
+        graph.mark_clean();
+        assert!(graph.add_weight(9, 1, Weight::ONE).is_err());
+        assert!(!graph.is_dirty());
+
+        graph.set_weighted(false);
+        assert!(graph.is_dirty());
+
+        // Imported graphs are built node by node, so they start dirty
+        assert!(campus(false).is_dirty());
+    }
+
+    #[test]
+    fn calculate_path_uses_negative_weights() {
+        let graph = build(true, &[1, 2, 3], &[(1, 3, 2), (1, 2, 5), (2, 3, -4)]);
+
+        let (weight, path) = graph.calculate_path(graph.node(1), graph.node(3)).unwrap();
+
+        assert_eq!(weight, Weight::Integer(1));
+        assert_eq!(codes(&path), vec![1, 2, 3]);
+        assert_eq!(
+            graph.bellman_ford(graph.node(1), graph.node(3)).unwrap().0,
+            Weight::Integer(1)
+        );
+    }
+
+    #[test]
+    fn bellman_ford_reports_negative_cycles() {
+        let graph = build(
+            true,
+            &[1, 2, 3, 4],
+            &[(1, 2, 1), (2, 3, -3), (3, 2, 1), (3, 4, 1)],
+        );
+
+        let Err(GraphError::NegativeCycle(cycle)) =
+            graph.bellman_ford(graph.node(1), graph.node(4))
+        else {
+            panic!("the cycle between 2 and 3 is negative");
+        };
+
+        assert_eq!(cycle.first(), cycle.last());
+        assert_eq!(cycle.len(), 3);
+        assert!(cycle.contains(&2) && cycle.contains(&3));
+
+        // An undirected negative edge is a cycle by itself
+        let graph = build(false, &[1, 2], &[(1, 2, -1)]);
+
+        assert!(matches!(
+            graph.calculate_path(graph.node(1), graph.node(2)),
+            Err(GraphError::NegativeCycle(_))
+        ));
+    }
+
+    #[test]
+    fn add_weight_rejects_zero_and_non_finite_weights() {
+        let mut graph = build(false, &[1, 2], &[(1, 2, 3)]);
+        graph.mark_clean();
+
+        for weight in [
+            Weight::ZERO,
+            Weight::Float(0.0),
+            Weight::Float(f64::NAN),
+            Weight::Float(f64::INFINITY),
+        ] {
+            assert!(matches!(
+                graph.add_weight(1, 2, weight),
+                Err(GraphError::InvalidWeight(_))
+            ));
+        }
+
+        assert!(!graph.is_dirty());
+        assert_eq!(
+            graph.find_edge_by_from_to(1, 2).unwrap().weight,
+            Weight::Integer(3)
+        );
+
+        graph.add_weight(2, 1, Weight::Float(-0.5)).unwrap();
+        assert_eq!(
+            graph.find_edge_by_from_to(1, 2).unwrap().weight,
+            Weight::Float(-0.5)
+        );
+    }
 }
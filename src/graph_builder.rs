@@ -1,51 +1,20 @@
-use crate::{feedback::Feedback, graph::*, node::*, GRAPH_SIZE};
+use crate::feedback::Feedback;
 use colored::*;
+use n1_project::{Attributes, Edge, Graph, Node, Weight};
 use std::{io, str::FromStr};
 
 pub fn init_graph() -> Graph {
-    let mut nodes = Vec::new();
-
-    let size = format!("{}", GRAPH_SIZE.to_string().green());
-    let text = format!("\nMonte seu grafo com {size} vértices").cyan();
-    println!("{}", text.bold().italic());
-
-    for i in 1..=GRAPH_SIZE {
-        let mut name = String::new();
-        let mut local_type = String::new();
-
-        print!("\n------------------\n");
-        println!("{}", format!("** Vértice {i}/{GRAPH_SIZE} **").blue());
-
-        loop {
-            let name: String = read_value("Nome do local:", &mut name, None);
-            let local_type: String = read_value("Tipo do local:", &mut local_type, None);
-
-            if nodes
-                .iter()
-                .map(|v: &Node| v.code)
-                .collect::<Vec<usize>>()
-                .contains(&i)
-            {
-                println!("{}", "Esse código já existe, tente usar outro".red());
-                continue;
-            }
+    println!("{}", "\nMonte seu grafo".cyan().bold().italic());
 
-            nodes.push(Node {
-                code: i,
-                name,
-                local_type,
-            });
+    println!();
+    let is_directed = read_yes_no("O grafo é direcionado? (s/n)");
 
-            break;
-        }
-    }
+    let mut graph = Graph::new(false, is_directed);
 
-    Graph {
-        is_weighted: false,
-        size: GRAPH_SIZE,
-        nodes,
-        edges: Vec::new(),
-    }
+    println!("\n{}", "** Vértices **".blue().bold());
+    read_nodes(&mut graph);
+
+    graph
 }
 
 fn read_value<T>(text: &str, value: &mut String, error_msg: Option<&str>) -> T
@@ -60,7 +29,7 @@ where
         println!("{}", text.yellow());
 
         io::stdin().read_line(value).unwrap();
-        println!("{}", Feedback::value_read(&value, "Valor digitado"));
+        println!("{}", Feedback::value_read(value, "Valor digitado"));
 
         if value.trim().is_empty() {
             println!("{}", error_msg.red());
@@ -77,94 +46,125 @@ where
     }
 }
 
-pub fn read_subgraph(is_weighted: bool) -> Graph {
+fn read_yes_no(text: &str) -> bool {
+    loop {
+        let mut answer = String::new();
+
+        println!("{}", text.yellow());
+
+        io::stdin().read_line(&mut answer).unwrap();
+        println!("{}", Feedback::value_read(&answer, "Valor digitado"));
+
+        match answer.trim() {
+            "s" => return true,
+            "n" => return false,
+            _ => {
+                println!("{}", "Por favor, digite s ou n".red());
+                continue;
+            }
+        }
+    }
+}
+
+pub fn read_subgraph(is_weighted: bool, is_directed: bool) -> Graph {
     println!("{}", "Monte seu subgrafo".cyan().bold().italic());
 
+    let mut graph = Graph::new(is_weighted, is_directed);
+
     println!("\n{}", "** Vértices **".blue().bold());
-    let nodes = read_nodes();
+    read_nodes(&mut graph);
 
     println!("{}", "Vértices lidos:".green());
-    print_nodes(&nodes);
-
-    let mut graph = Graph {
-        is_weighted,
-        size: nodes.len(),
-        nodes,
-        edges: Vec::new(),
-    };
+    print_nodes(&graph);
 
     println!("\n-----------------");
     println!("{}", "** Arestas **".blue().bold());
     read_edges(&mut graph, is_weighted);
 
     println!("{}", "Subgrafo criado:".green());
-    println!("{graph}\n");
+    println!("{}\n", Feedback::format_graph(&graph));
 
     graph
 }
 
-fn read_nodes() -> Vec<Node> {
-    let mut nodes = Vec::new();
-
+fn read_nodes(graph: &mut Graph) {
     loop {
-        let mut name = String::new();
-        let mut local_type = String::new();
-
         println!("-----------------");
 
-        let code = loop {
-            let mut code = String::new();
+        let can_quit = graph.size() > 0;
+
+        match read_new_node(graph, can_quit) {
+            Some(node) => graph.add_node(node).unwrap(),
+            None => return,
+        }
+    }
+}
 
-            let quit_opt = format!("{}", "q".purple());
-            let text = format!(
-                "Digite o código do vértice ou {quit_opt} para terminar a criação dos vértices:"
-            );
-            println!("{}", text.yellow());
+/// Reads a node with a code not used in the graph, returns `None` when the
+/// user types `q` and `can_quit` is set
+pub fn read_new_node(graph: &Graph, can_quit: bool) -> Option<Node> {
+    let mut name = String::new();
+    let mut local_type = String::new();
 
-            io::stdin().read_line(&mut code).unwrap();
-            println!("{}", Feedback::value_read(&code, "Código digitado"));
+    let code = loop {
+        let mut code = String::new();
 
-            if code.trim() == "q" {
-                if nodes.is_empty() {
-                    println!("{}\n", "O grafo deve ter pelo menos um vértice!".red());
-                    continue;
-                }
+        let quit_opt = format!("{}", "q".purple());
+        let text = format!(
+            "Digite o código do vértice ou {quit_opt} para terminar a criação dos vértices:"
+        );
+        println!("{}", text.yellow());
+
+        io::stdin().read_line(&mut code).unwrap();
+        println!("{}", Feedback::value_read(&code, "Código digitado"));
 
-                return nodes;
+        if code.trim() == "q" {
+            if !can_quit {
+                println!("{}\n", "O grafo deve ter pelo menos um vértice!".red());
+                continue;
             }
 
-            match code.trim().parse() {
-                Ok(parsed_code) if parsed_code > 0 => {
-                    if node_exists(&nodes, parsed_code) {
-                        println!("{}\n", "Erro, um vértice com esse código já existe!".red());
-                        continue;
-                    }
+            return None;
+        }
 
-                    break parsed_code;
-                }
-                _ => {
-                    println!(
-                        "{}\n",
-                        "Erro, o código precisa ser um número inteiro maior que 0".red()
-                    );
+        match code.trim().parse() {
+            Ok(parsed_code) if parsed_code > 0 => {
+                if graph.find_by_code(parsed_code).is_some() {
+                    println!("{}\n", "Erro, um vértice com esse código já existe!".red());
                     continue;
                 }
+
+                break parsed_code;
             }
-        };
+            _ => {
+                println!(
+                    "{}\n",
+                    "Erro, o código precisa ser um número inteiro maior que 0".red()
+                );
+                continue;
+            }
+        }
+    };
 
-        let name: String = read_value("Nome do local:", &mut name, None);
-        let local_type: String = read_value("Tipo do local:", &mut local_type, None);
+    let name: String = read_value("Nome do local:", &mut name, None);
+    let local_type: String = read_value("Tipo do local:", &mut local_type, None);
 
-        nodes.push(Node {
-            code,
-            name,
-            local_type,
-        });
-    }
+    Some(Node {
+        code,
+        name,
+        local_type,
+        attributes: Attributes::new(),
+    })
+}
+
+pub fn read_node_name() -> String {
+    let mut name = String::new();
+
+    read_value("Novo nome do local:", &mut name, None)
 }
 
 fn read_edges(graph: &mut Graph, is_weighted: bool) {
-    let default_weight = 1;
+    let default_weight = Weight::ONE;
 
     let quit_opt = format!("{}", "q".purple());
     let text = format!("Digite {quit_opt} a qualquer momento para terminar a criação das arestas");
@@ -174,16 +174,16 @@ fn read_edges(graph: &mut Graph, is_weighted: bool) {
         let mut from = String::new();
         let mut to = String::new();
 
-        print!("-----------------\n");
+        println!("-----------------");
 
         println!("{}", "Primeiro vértice da aresta".blue());
-        let from = match read_code(&graph.nodes, &mut from) {
+        let from = match read_code(graph, &mut from) {
             Some(from) => from,
             None => break,
         };
 
         println!("{}", "Segundo vértice da aresta".blue());
-        let to = match read_code(&graph.nodes, &mut to) {
+        let to = match read_code(graph, &mut to) {
             Some(to) => to,
             None => break,
         };
@@ -203,12 +203,13 @@ fn read_edges(graph: &mut Graph, is_weighted: bool) {
                 from,
                 to,
                 weight: weight.unwrap(),
+                attributes: Attributes::new(),
             })
             .unwrap_or_else(|_err| println!("{}", "Aresta já existe!".red()));
     }
 }
 
-fn read_code(nodes: &Vec<Node>, code: &mut String) -> Option<usize> {
+fn read_code(graph: &Graph, code: &mut String) -> Option<usize> {
     loop {
         *code = String::new();
 
@@ -223,7 +224,7 @@ fn read_code(nodes: &Vec<Node>, code: &mut String) -> Option<usize> {
 
         match code.trim().parse() {
             Ok(parsed_code) if parsed_code > 0 => {
-                if !node_exists(nodes, parsed_code) {
+                if graph.find_by_code(parsed_code).is_none() {
                     println!(
                         "{}\n",
                         "Erro, nenhum vértice encontrado com o código informado".red()
@@ -247,11 +248,11 @@ fn read_code(nodes: &Vec<Node>, code: &mut String) -> Option<usize> {
     }
 }
 
-fn read_weight() -> Option<u32> {
+fn read_weight() -> Option<Weight> {
     loop {
         let mut weight = String::new();
 
-        println!("{}", "Digite o peso da aresta:".yellow());
+        println!("{}", Feedback::read_weight());
 
         io::stdin().read_line(&mut weight).unwrap();
         println!("{}", Feedback::value_read(&weight, "Peso digitado"));
@@ -260,25 +261,25 @@ fn read_weight() -> Option<u32> {
             return None;
         }
 
-        match weight.trim().parse() {
-            Ok(parsed_weight) if parsed_weight > 0 => {
+        // Accepts a decimal comma too, e.g. 2,5
+        match weight.trim().replace(',', ".").parse::<Weight>() {
+            Ok(parsed_weight) if !parsed_weight.is_zero() => {
                 break Some(parsed_weight);
             }
 
             _ => {
-                println!("{}", "Erro, o peso precisa ser maior que 0".red());
+                println!(
+                    "{}",
+                    "Erro, o peso precisa ser um número diferente de 0".red()
+                );
                 continue;
             }
         }
     }
 }
 
-fn node_exists(nodes: &Vec<Node>, code: usize) -> bool {
-    nodes.iter().find(|n| n.code == code).is_some()
-}
-
-fn print_nodes(nodes: &Vec<Node>) {
-    for node in nodes.iter() {
-        println!("{node}");
+fn print_nodes(graph: &Graph) {
+    for node in graph.nodes() {
+        println!("{}", Feedback::format_node(node));
     }
 }
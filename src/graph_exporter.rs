@@ -1,36 +1,315 @@
-use std::{error::Error, fs, process::Command};
+use std::{
+    error::Error,
+    fs, io,
+    path::{Path, PathBuf},
+    process::Command,
+};
 
-use crate::graph::Graph;
+use crate::{
+    error::GraphError,
+    gexf,
+    graph::Graph,
+    graphml,
+    node::Attributes,
+    svg_renderer::{self, Layout},
+};
 
-const DOT_INPUT: &str = "graph.dot";
-pub const DOT_OUTPUT: &str = "graph.png";
+/// How the exported image is drawn
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum Backend {
+    /// SVG rendered in process, doesn't need any external program
+    Svg(Layout),
+    /// Rendered by the `dot` program from graphviz
+    Graphviz(DotOptions),
+}
+
+impl Backend {
+    pub fn extension(&self) -> &'static str {
+        match self {
+            Backend::Svg(_) => "svg",
+            Backend::Graphviz(options) => options.format.extension(),
+        }
+    }
+}
+
+/// Output format passed to `dot` as `-T<format>`
+#[derive(Clone, Copy, PartialEq, Debug, Default)]
+pub enum DotFormat {
+    #[default]
+    Png,
+    Svg,
+    Pdf,
+}
+
+impl DotFormat {
+    pub fn extension(&self) -> &'static str {
+        match self {
+            DotFormat::Png => "png",
+            DotFormat::Svg => "svg",
+            DotFormat::Pdf => "pdf",
+        }
+    }
+}
+
+/// Styling of the DOT source, the default is the plain output with labels only
+#[derive(Clone, Copy, PartialEq, Debug, Default)]
+pub struct DotOptions {
+    pub format: DotFormat,
+    /// Fills the vertices with one color per `local_type`
+    pub color_by_type: bool,
+    /// Draws the vertices with one shape per `local_type`
+    pub shape_by_type: bool,
+    /// Heavier edges are drawn longer (`minlen`) and thicker (`penwidth`),
+    /// only used in weighted graphs
+    pub scale_by_weight: bool,
+}
+
+const TYPE_COLORS: [&str; 8] = [
+    "lightblue",
+    "palegreen",
+    "lightsalmon",
+    "khaki",
+    "plum",
+    "lightgray",
+    "pink",
+    "aquamarine",
+];
 
-pub fn export_graph(graph: &Graph) -> Result<(), Box<dyn Error>> {
+const TYPE_SHAPES: [&str; 8] = [
+    "ellipse",
+    "box",
+    "diamond",
+    "hexagon",
+    "octagon",
+    "triangle",
+    "house",
+    "parallelogram",
+];
+
+/// Whether `write_graph` can save to `path`, formats that lose information,
+/// like DOT, can only be imported
+pub fn can_write(path: &Path) -> bool {
+    !matches!(
+        path.extension().and_then(|ext| ext.to_str()),
+        Some("dot" | "gv")
+    )
+}
+
+/// Saves the graph choosing the format by the file extension, files without a
+/// known extension are saved as JSON
+pub fn write_graph(graph: &Graph, path: &Path) -> Result<(), GraphError> {
+    match path.extension().and_then(|ext| ext.to_str()) {
+        Some("graphml") => fs::write(path, graphml::to_graphml(graph))?,
+        Some("gexf") => fs::write(path, gexf::to_gexf(graph))?,
+        Some(extension @ ("dot" | "gv")) => {
+            return Err(GraphError::UnsupportedFormat(extension.to_string()))
+        }
+        _ => graph.save(path)?,
+    }
+
+    Ok(())
+}
+
+/// Path of the image exported for the graph saved in `graph_file`, e.g.
+/// `campus.json` is exported as `campus.svg` or `campus.png`
+pub fn output_path(graph_file: &Path, backend: Backend) -> PathBuf {
+    graph_file.with_extension(backend.extension())
+}
+
+/// Exports the graph to `output`, edges that are also in `highlight` are drawn in red
+pub fn export_graph(
+    graph: &Graph,
+    highlight: Option<&Graph>,
+    output: &Path,
+    backend: Backend,
+) -> Result<(), Box<dyn Error>> {
+    match backend {
+        Backend::Svg(layout) => {
+            fs::write(output, svg_renderer::render_svg(graph, highlight, layout))?;
+            Ok(())
+        }
+        Backend::Graphviz(options) => export_with_graphviz(graph, highlight, output, options),
+    }
+}
+
+/// DOT source of the graph, edges that are also in `highlight` are drawn in red
+pub fn to_dot(graph: &Graph, highlight: Option<&Graph>, options: DotOptions) -> String {
     let mut f = String::new();
 
-    f.push_str("graph { \n");
+    let (graph_type, edge_op) = if graph.is_directed() {
+        ("digraph", "->")
+    } else {
+        ("graph", "--")
+    };
 
-    for node in &graph.nodes {
-        f = format!("{f}    {} [label=\"{}\"]\n", node.code, node.name);
+    f = format!("{f}{graph_type} {{ \n");
+
+    if options.color_by_type {
+        f = format!("{f}    node [style=filled]\n");
     }
 
-    for edge in &graph.edges {
-        f = format!("{f}    {} -- {}", edge.from, edge.to);
+    // Sorted so each type keeps its color and shape between exports
+    let mut types: Vec<&str> = graph.nodes().map(|node| node.local_type.as_str()).collect();
+    types.sort();
+    types.dedup();
+
+    for node in graph.nodes() {
+        let mut attributes = dot_attributes(&node.attributes);
+        attributes.push(format!("label=\"{}\"", escape(&node.name)));
+        let type_index = types.binary_search(&node.local_type.as_str()).unwrap();
 
-        if graph.is_weighted {
-            f = format!("{f} [label=\"{}\"]", edge.weight);
+        if options.color_by_type {
+            attributes.push(format!(
+                "fillcolor={}",
+                TYPE_COLORS[type_index % TYPE_COLORS.len()]
+            ));
+        }
+
+        if options.shape_by_type {
+            attributes.push(format!(
+                "shape={}",
+                TYPE_SHAPES[type_index % TYPE_SHAPES.len()]
+            ));
+        }
+
+        f = format!("{f}    {} [{}]\n", node.code, attributes.join(","));
+    }
+
+    let min_weight = graph
+        .edges()
+        .map(|edge| edge.weight.as_f64())
+        .fold(f64::INFINITY, f64::min);
+    let max_weight = graph
+        .edges()
+        .map(|edge| edge.weight.as_f64())
+        .fold(f64::NEG_INFINITY, f64::max);
+
+    for edge in graph.edges() {
+        let mut attributes = dot_attributes(&edge.attributes);
+
+        if graph.is_weighted() {
+            attributes.push(format!("label=\"{}\"", edge.weight));
+        }
+
+        let is_highlighted =
+            highlight.is_some_and(|h| h.find_edge_by_from_to(edge.from, edge.to).is_some());
+
+        if options.scale_by_weight && graph.is_weighted() {
+            // 0 for the lightest edge and 1 for the heaviest
+            let scale = if max_weight > min_weight {
+                (edge.weight.as_f64() - min_weight) / (max_weight - min_weight)
+            } else {
+                0.0
+            };
+
+            attributes.push(format!("minlen={}", (scale * 3.0).round() as u32 + 1));
+
+            if !is_highlighted {
+                attributes.push(format!("penwidth={:.1}", 1.0 + scale * 3.0));
+            }
+        }
+
+        if is_highlighted {
+            attributes.push("color=red,penwidth=3".to_string());
+        }
+
+        f = format!("{f}    {} {edge_op} {}", edge.from, edge.to);
+
+        if !attributes.is_empty() {
+            f = format!("{f} [{}]", attributes.join(","));
         }
 
         f = format!("{f}\n")
     }
 
-    f.push_str("}");
+    f.push('}');
+
+    f
+}
+
+/// Writes the DOT source next to `output`, with the `.dot` extension, and
+/// renders it with `dot`
+fn export_with_graphviz(
+    graph: &Graph,
+    highlight: Option<&Graph>,
+    output: &Path,
+    options: DotOptions,
+) -> Result<(), Box<dyn Error>> {
+    let dot_input = output.with_extension("dot");
 
-    fs::write(DOT_INPUT, f)?;
+    fs::write(&dot_input, to_dot(graph, highlight, options))?;
 
-    let dot_output = Command::new("dot").args(["-Tpng", DOT_INPUT]).output()?;
+    let format = format!("-T{}", options.format.extension());
 
-    fs::write(DOT_OUTPUT, dot_output.stdout)?;
+    let dot_output = match Command::new("dot").arg(format).arg(&dot_input).output() {
+        Ok(dot_output) => dot_output,
+        Err(err) if err.kind() == io::ErrorKind::NotFound => {
+            return Err("o programa dot do graphviz não foi encontrado".into());
+        }
+        Err(err) => return Err(err.into()),
+    };
+
+    if !dot_output.status.success() {
+        let stderr = String::from_utf8_lossy(&dot_output.stderr);
+        return Err(format!("o dot falhou: {}", stderr.trim()).into());
+    }
+
+    fs::write(output, dot_output.stdout)?;
 
     Ok(())
 }
+
+/// The node and edge attributes as quoted DOT attributes. They come before
+/// the ones set by the export, which take precedence when the keys clash
+fn dot_attributes(attributes: &Attributes) -> Vec<String> {
+    attributes
+        .iter()
+        .map(|(key, value)| format!("\"{}\"=\"{}\"", escape(key), escape(value)))
+        .collect()
+}
+
+fn escape(text: &str) -> String {
+    text.replace('\\', "\\\\").replace('"', "\\\"")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::fixtures::campus;
+
+    #[test]
+    fn to_dot_styles_types_weights_and_the_highlight() {
+        let graph = campus(false);
+        let highlight = graph.path_subgraph(&[
+            graph.find_by_code(1).unwrap(),
+            graph.find_by_code(2).unwrap(),
+        ]);
+        let options = DotOptions {
+            color_by_type: true,
+            shape_by_type: true,
+            scale_by_weight: true,
+            ..DotOptions::default()
+        };
+
+        let dot = to_dot(&graph, Some(&highlight), options);
+
+        assert!(dot.starts_with("graph {"));
+        assert!(dot.contains("node [style=filled]"));
+        // Types sorted as "", "sala" and "área"
+        assert!(dot.contains(r#"label="Sala \"A\", <1> & 2",fillcolor=palegreen,shape=box"#));
+        assert!(dot.contains(r#"label="Portão",fillcolor=lightblue,shape=ellipse"#));
+        assert!(
+            dot.contains(r#"1 -- 2 ["coberta"="sim",label="2.5",minlen=4,color=red,penwidth=3]"#)
+        );
+        assert!(dot.contains(r#"2 -- 5 [label="-3",minlen=1,penwidth=1.0]"#));
+    }
+
+    #[test]
+    fn to_dot_skips_the_styles_by_default() {
+        let dot = to_dot(&campus(true), None, DotOptions::default());
+
+        assert!(dot.starts_with("digraph {"));
+        assert!(!dot.contains("fillcolor") && !dot.contains("minlen") && !dot.contains("red"));
+        assert!(dot.contains(r#"5 -> 5 [label="1"]"#));
+    }
+}
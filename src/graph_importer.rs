@@ -0,0 +1,82 @@
+use std::{
+    collections::{HashMap, HashSet},
+    fs,
+    path::Path,
+};
+
+use crate::{dot_parser, error::GraphError, gexf, graph::Graph, graphml, weight::Weight};
+
+/// Extensions accepted by `import_graph`
+pub const SUPPORTED_EXTENSIONS: [&str; 5] = ["json", "dot", "gv", "graphml", "gexf"];
+
+/// Reads a graph choosing the format by the file extension, files without a
+/// known extension are read as JSON
+pub fn import_graph(path: &Path) -> Result<Graph, GraphError> {
+    match path.extension().and_then(|ext| ext.to_str()) {
+        Some("dot" | "gv") => dot_parser::parse_dot(&fs::read_to_string(path)?),
+        Some("graphml") => graphml::parse_graphml(&fs::read_to_string(path)?),
+        Some("gexf") => gexf::parse_gexf(&fs::read_to_string(path)?),
+        _ => Graph::load(path),
+    }
+}
+
+/// Maps the node ids of an imported file to codes. Ids that are valid codes
+/// are kept, unless an earlier id has the same code (e.g. `7` and `07`), the
+/// others get the next free codes in order
+pub(crate) fn assign_codes<'a>(ids: impl Iterator<Item = &'a str>) -> HashMap<String, usize> {
+    let ids: Vec<&str> = ids.collect();
+
+    let mut codes: HashMap<String, usize> = HashMap::new();
+    let mut used = HashSet::new();
+
+    for id in &ids {
+        let Some(code) = id.parse().ok().filter(|&code| code > 0) else {
+            continue;
+        };
+
+        if !codes.contains_key(*id) && used.insert(code) {
+            codes.insert(id.to_string(), code);
+        }
+    }
+
+    let mut next_code = used.iter().max().copied().unwrap_or(0) + 1;
+
+    for id in ids {
+        codes.entry(id.to_string()).or_insert_with(|| {
+            next_code += 1;
+            next_code - 1
+        });
+    }
+
+    codes
+}
+
+/// Weights are numbers other than 0. Tools like Gephi write integers as
+/// `5.0`, they're read back as integers
+pub(crate) fn parse_weight(text: &str) -> Option<Weight> {
+    let weight = match text.parse().ok()? {
+        Weight::Float(weight) if weight.fract() == 0.0 && weight.abs() < i64::MAX as f64 => {
+            Weight::Integer(weight as i64)
+        }
+        weight => weight,
+    };
+
+    (!weight.is_zero()).then_some(weight)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn assign_codes_gives_colliding_ids_free_codes() {
+        let codes = assign_codes(["7", "07", "sala", "3", "0", "7"].into_iter());
+
+        assert_eq!(codes["7"], 7);
+        assert_eq!(codes["3"], 3);
+        assert_eq!(codes["07"], 8);
+        assert_eq!(codes["sala"], 9);
+        assert_eq!(codes["0"], 10);
+        assert_eq!(codes.len(), 5);
+    }
+}
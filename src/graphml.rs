@@ -0,0 +1,355 @@
+use std::collections::HashMap;
+
+use crate::{
+    error::GraphError,
+    graph::{Edge, Graph},
+    graph_importer,
+    node::{Attributes, Node},
+    weight::Weight,
+    xml::{self, escape},
+};
+
+/// GraphML document of the graph, the node name and `local_type`, the edge
+/// weight and the attributes are written as `<data>` with their own `<key>`
+pub fn to_graphml(graph: &Graph) -> String {
+    let edge_default = if graph.is_directed() {
+        "directed"
+    } else {
+        "undirected"
+    };
+
+    let node_keys = attribute_keys(graph.nodes().map(|node| &node.attributes));
+    let edge_keys = attribute_keys(graph.edges().map(|edge| &edge.attributes));
+
+    let mut f = String::from(
+        "<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n<graphml xmlns=\"http://graphml.graphdrawing.org/xmlns\">\n",
+    );
+
+    f.push_str("  <key id=\"name\" for=\"node\" attr.name=\"name\" attr.type=\"string\"/>\n");
+    f.push_str(
+        "  <key id=\"local_type\" for=\"node\" attr.name=\"local_type\" attr.type=\"string\"/>\n",
+    );
+
+    if graph.is_weighted() {
+        // Integer weights keep the type they always had
+        let weight_type = if graph
+            .edges()
+            .any(|edge| matches!(edge.weight, Weight::Float(_)))
+        {
+            "double"
+        } else {
+            "int"
+        };
+
+        f.push_str(&format!(
+            "  <key id=\"weight\" for=\"edge\" attr.name=\"weight\" attr.type=\"{weight_type}\"/>\n"
+        ));
+    }
+
+    for (domain, keys) in [("node", &node_keys), ("edge", &edge_keys)] {
+        for key in keys {
+            f.push_str(&format!(
+                "  <key id=\"{domain}.{0}\" for=\"{domain}\" attr.name=\"{0}\" attr.type=\"string\"/>\n",
+                escape(key)
+            ));
+        }
+    }
+
+    f.push_str(&format!(
+        "  <graph id=\"G\" edgedefault=\"{edge_default}\">\n"
+    ));
+
+    for node in graph.nodes() {
+        f.push_str(&format!(
+            "    <node id=\"{}\">\n      <data key=\"name\">{}</data>\n      <data key=\"local_type\">{}</data>\n",
+            node.code,
+            escape(&node.name),
+            escape(&node.local_type)
+        ));
+        f.push_str(&attribute_data("node", &node.attributes));
+        f.push_str("    </node>\n");
+    }
+
+    for edge in graph.edges() {
+        if !graph.is_weighted() && edge.attributes.is_empty() {
+            f.push_str(&format!(
+                "    <edge source=\"{}\" target=\"{}\"/>\n",
+                edge.from, edge.to
+            ));
+            continue;
+        }
+
+        f.push_str(&format!(
+            "    <edge source=\"{}\" target=\"{}\">\n",
+            edge.from, edge.to
+        ));
+
+        if graph.is_weighted() {
+            f.push_str(&format!(
+                "      <data key=\"weight\">{}</data>\n",
+                edge.weight
+            ));
+        }
+
+        f.push_str(&attribute_data("edge", &edge.attributes));
+        f.push_str("    </edge>\n");
+    }
+
+    f.push_str("  </graph>\n</graphml>\n");
+
+    f
+}
+
+/// Builds a graph from a GraphML document, only the first `<graph>` is read.
+///
+/// Node data named `name` (or `label`) and `local_type` (or `type`) and edge
+/// data named `weight` are used, by their `attr.name`, any other data with
+/// text becomes an attribute. Numeric node ids are used as codes, the others
+/// get the next free codes. The graph is weighted when a `weight` key is
+/// declared for edges or any edge has a weight, edges without one get weight 1
+pub fn parse_graphml(source: &str) -> Result<Graph, GraphError> {
+    let root = xml::parse(source)?;
+
+    if root.local_name() != "graphml" {
+        return Err(root.error("esperado o elemento <graphml>"));
+    }
+
+    let mut keys: HashMap<&str, Key> = HashMap::new();
+
+    for key in root.children_named("key") {
+        let id = key.required_attribute("id")?;
+
+        keys.insert(
+            id,
+            Key {
+                name: key.attribute("attr.name").unwrap_or(id),
+                domain: key.attribute("for").unwrap_or("all"),
+                default: key.child("default").map(|default| default.text.trim()),
+            },
+        );
+    }
+
+    let graph_element = root
+        .child("graph")
+        .ok_or_else(|| root.error("esperado o elemento <graph>"))?;
+
+    let is_directed = graph_element.attribute("edgedefault") == Some("directed");
+
+    // Values of the element data by key name, with the defaults of the keys
+    // of its domain filled in
+    let data_of = |element: &xml::Element| -> Vec<(String, String)> {
+        let domain = element.local_name();
+
+        let mut values: Vec<(String, String)> = element
+            .children_named("data")
+            .filter(|data| data.children.is_empty() && !data.text.trim().is_empty())
+            .filter_map(|data| {
+                let key = data.attribute("key")?;
+                let name = keys.get(key).map_or(key, |key| key.name);
+
+                Some((name.to_string(), data.text.trim().to_string()))
+            })
+            .collect();
+
+        for key in keys.values() {
+            if let Some(default) = key.default {
+                let applies = key.domain == domain || key.domain == "all";
+
+                if applies && values.iter().all(|(name, _)| name != key.name) {
+                    values.push((key.name.to_string(), default.to_string()));
+                }
+            }
+        }
+
+        values
+    };
+
+    let node_elements: Vec<&xml::Element> = graph_element.children_named("node").collect();
+    let edge_elements: Vec<&xml::Element> = graph_element.children_named("edge").collect();
+
+    let mut ids = Vec::new();
+
+    for node in &node_elements {
+        ids.push(node.required_attribute("id")?);
+    }
+
+    let codes = graph_importer::assign_codes(ids.iter().copied());
+
+    let mut edge_values = Vec::new();
+
+    for edge in &edge_elements {
+        let mut data = Data(data_of(edge));
+
+        let weight = match data.take(&["weight"]) {
+            Some(weight) => Some(graph_importer::parse_weight(&weight).ok_or_else(|| {
+                edge.error(&format!(
+                    "o peso da aresta precisa ser um número diferente de 0: {weight}"
+                ))
+            })?),
+            None => None,
+        };
+
+        edge_values.push((weight, data));
+    }
+
+    let is_weighted = keys
+        .values()
+        .any(|key| key.name == "weight" && (key.domain == "edge" || key.domain == "all"))
+        || edge_values.iter().any(|(weight, _)| weight.is_some());
+    let mut graph = Graph::new(is_weighted, is_directed);
+
+    for (node, id) in node_elements.iter().zip(&ids) {
+        let mut data = Data(data_of(node));
+
+        graph.add_node(Node {
+            code: codes[*id],
+            name: data.take(&["name", "label"]).unwrap_or(id.to_string()),
+            local_type: data.take(&["local_type", "type"]).unwrap_or_default(),
+            attributes: data.into_attributes(),
+        })?;
+    }
+
+    for (edge, (weight, data)) in edge_elements.iter().zip(edge_values) {
+        let code_of = |attribute: &str| -> Result<usize, GraphError> {
+            let id = edge.required_attribute(attribute)?;
+
+            codes.get(id).copied().ok_or_else(|| {
+                edge.error(&format!(
+                    "a aresta usa o vértice {id}, que não foi declarado"
+                ))
+            })
+        };
+
+        graph.add_edge(Edge {
+            from: code_of("source")?,
+            to: code_of("target")?,
+            weight: weight.unwrap_or(Weight::ONE),
+            attributes: data.into_attributes(),
+        })?;
+    }
+
+    Ok(graph)
+}
+
+struct Key<'a> {
+    name: &'a str,
+    /// `node`, `edge`, `graph` or `all`
+    domain: &'a str,
+    default: Option<&'a str>,
+}
+
+/// Data values of a node or edge by key name
+struct Data(Vec<(String, String)>);
+
+impl Data {
+    /// Removes and returns the first value named one of `names`
+    fn take(&mut self, names: &[&str]) -> Option<String> {
+        let position = self
+            .0
+            .iter()
+            .position(|(name, _)| names.contains(&name.as_str()))?;
+
+        Some(self.0.remove(position).1)
+    }
+
+    fn into_attributes(self) -> Attributes {
+        self.0.into_iter().collect()
+    }
+}
+
+/// Sorted keys used in any of `attributes`
+fn attribute_keys<'a>(attributes: impl Iterator<Item = &'a Attributes>) -> Vec<&'a str> {
+    let mut keys: Vec<&str> = attributes
+        .flat_map(|attributes| attributes.keys())
+        .map(String::as_str)
+        .collect();
+
+    keys.sort_unstable();
+    keys.dedup();
+    keys
+}
+
+fn attribute_data(domain: &str, attributes: &Attributes) -> String {
+    attributes
+        .iter()
+        .map(|(key, value)| {
+            format!(
+                "      <data key=\"{domain}.{}\">{}</data>\n",
+                escape(key),
+                escape(value)
+            )
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::fixtures::campus;
+
+    #[test]
+    fn round_trips_the_graph() {
+        for is_directed in [false, true] {
+            let graph = campus(is_directed);
+            let parsed = parse_graphml(&to_graphml(&graph)).unwrap();
+
+            assert_eq!(parsed.to_json(), graph.to_json());
+        }
+    }
+
+    #[test]
+    fn reads_keys_by_name_with_their_defaults() {
+        let source = r#"<?xml version="1.0"?>
+            <graphml xmlns="http://graphml.graphdrawing.org/xmlns">
+              <key id="d0" for="node" attr.name="label"/>
+              <key id="d1" for="all" attr.name="andar"><default>1</default></key>
+              <key id="d2" for="edge" attr.name="weight"><default>2</default></key>
+              <graph edgedefault="directed">
+                <node id="entrada"><data key="d0">Sala &amp; &lt;Pátio&gt; &#233;</data></node>
+                <node id="7"><data key="d1">3</data></node>
+                <edge source="entrada" target="7"/>
+                <edge source="7" target="entrada"><data key="d2">0.5</data></edge>
+              </graph>
+            </graphml>"#;
+
+        let graph = parse_graphml(source).unwrap();
+        let entrada = graph.find_by_code(8).unwrap();
+
+        assert!(graph.is_directed() && graph.is_weighted());
+        assert_eq!(entrada.name, "Sala & <Pátio> é");
+        assert_eq!(entrada.attributes["andar"], "1");
+        assert_eq!(graph.find_by_code(7).unwrap().attributes["andar"], "3");
+        assert_eq!(
+            graph.find_edge_by_from_to(8, 7).unwrap().weight,
+            Weight::Integer(2)
+        );
+        assert_eq!(
+            graph.find_edge_by_from_to(7, 8).unwrap().weight,
+            Weight::Float(0.5)
+        );
+        assert_eq!(
+            graph.find_edge_by_from_to(8, 7).unwrap().attributes["andar"],
+            "1"
+        );
+    }
+
+    #[test]
+    fn a_declared_weight_key_makes_the_graph_weighted() {
+        let source = r#"<graphml>
+              <key id="w" for="edge" attr.name="weight" attr.type="int"/>
+              <graph edgedefault="undirected"><node id="1"/></graph>
+            </graphml>"#;
+
+        assert!(parse_graphml(source).unwrap().is_weighted());
+
+        let mut graph = Graph::new(true, false);
+        graph
+            .add_node(campus(false).find_by_code(5).unwrap().clone())
+            .unwrap();
+
+        assert!(parse_graphml(&to_graphml(&graph)).unwrap().is_weighted());
+        assert!(!parse_graphml("<graphml><graph/></graphml>")
+            .unwrap()
+            .is_weighted());
+    }
+}
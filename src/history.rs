@@ -0,0 +1,523 @@
+use crate::{
+    error::GraphError,
+    graph::{Edge, Graph},
+    node::Node,
+    weight::Weight,
+};
+
+/// A single mutation of the graph. Applying it returns the change that
+/// reverts it, which is how `History` undoes and redoes
+#[derive(Debug, Clone, PartialEq)]
+pub enum Change {
+    /// Adds the node and then the edges, which were incident to it when it
+    /// was removed, repeated ones included. The edges don't get their old
+    /// indices back, the graph lists them sorted by their ends so the order
+    /// doesn't change
+    AddNode {
+        node: Node,
+        edges: Vec<Edge>,
+    },
+    /// Removes the node and its incident edges
+    RemoveNode(usize),
+    RenameNode {
+        code: usize,
+        name: String,
+    },
+    AddEdge(Edge),
+    RemoveEdge {
+        from: usize,
+        to: usize,
+    },
+    SetWeight {
+        from: usize,
+        to: usize,
+        weight: Weight,
+    },
+    SetWeighted(bool),
+    /// `None` removes the attribute
+    SetNodeAttribute {
+        code: usize,
+        key: String,
+        value: Option<String>,
+    },
+    /// `None` removes the attribute
+    SetEdgeAttribute {
+        from: usize,
+        to: usize,
+        key: String,
+        value: Option<String>,
+    },
+}
+
+impl Change {
+    /// Applies the change and returns its inverse
+    pub fn apply(self, graph: &mut Graph) -> Result<Change, GraphError> {
+        let inverse = match self {
+            Change::AddNode { node, edges } => {
+                let code = node.code;
+                graph.add_node(node)?;
+
+                for edge in edges {
+                    if let Err(err) = graph.insert_edge(edge) {
+                        graph.remove_node(code)?;
+                        return Err(err);
+                    }
+                }
+
+                Change::RemoveNode(code)
+            }
+            Change::RemoveNode(code) => {
+                let (node, edges) = graph.remove_node(code)?;
+
+                Change::AddNode { node, edges }
+            }
+            Change::RenameNode { code, name } => {
+                let old_name = graph
+                    .find_by_code(code)
+                    .ok_or(GraphError::NodeNotFound(code))?
+                    .name
+                    .clone();
+
+                graph.rename_node(code, name)?;
+
+                Change::RenameNode {
+                    code,
+                    name: old_name,
+                }
+            }
+            Change::AddEdge(edge) => {
+                let (from, to) = (edge.from, edge.to);
+                graph.add_edge(edge)?;
+
+                Change::RemoveEdge { from, to }
+            }
+            Change::RemoveEdge { from, to } => Change::AddEdge(graph.remove_edge(from, to)?),
+            Change::SetWeight { from, to, weight } => {
+                let old_weight = graph
+                    .find_edge_by_from_to(from, to)
+                    .ok_or(GraphError::EdgeNotFound { from, to })?
+                    .weight;
+
+                graph.add_weight(from, to, weight)?;
+
+                Change::SetWeight {
+                    from,
+                    to,
+                    weight: old_weight,
+                }
+            }
+            Change::SetWeighted(is_weighted) => {
+                let was_weighted = graph.is_weighted();
+                graph.set_weighted(is_weighted);
+
+                Change::SetWeighted(was_weighted)
+            }
+            Change::SetNodeAttribute { code, key, value } => {
+                let old_value = match value {
+                    Some(value) => graph.set_node_attribute(code, key.clone(), value)?,
+                    None => graph.remove_node_attribute(code, &key)?,
+                };
+
+                Change::SetNodeAttribute {
+                    code,
+                    key,
+                    value: old_value,
+                }
+            }
+            Change::SetEdgeAttribute {
+                from,
+                to,
+                key,
+                value,
+            } => {
+                let old_value = match value {
+                    Some(value) => graph.set_edge_attribute(from, to, key.clone(), value)?,
+                    None => graph.remove_edge_attribute(from, to, &key)?,
+                };
+
+                Change::SetEdgeAttribute {
+                    from,
+                    to,
+                    key,
+                    value: old_value,
+                }
+            }
+        };
+
+        Ok(inverse)
+    }
+
+    /// Changes that multiply every edge weight by `factor`, see
+    /// `Weight::scale`. Fails when a weight would overflow or become 0
+    pub fn scale_weights(graph: &Graph, factor: f64) -> Result<Vec<Change>, GraphError> {
+        if !factor.is_finite() || factor <= 0.0 {
+            return Err(GraphError::InvalidScaleFactor(factor));
+        }
+
+        graph
+            .edges()
+            .map(|edge| {
+                Ok(Change::SetWeight {
+                    from: edge.from,
+                    to: edge.to,
+                    weight: edge.weight.scale(factor).ok_or(
+                        GraphError::ScaledWeightOutOfRange {
+                            from: edge.from,
+                            to: edge.to,
+                        },
+                    )?,
+                })
+            })
+            .collect()
+    }
+
+    /// Changes that make the graph unweighted, the weights go back to 1 like
+    /// the ones of edges created in unweighted graphs
+    pub fn remove_weights(graph: &Graph) -> Vec<Change> {
+        let weights = graph.edges().map(|edge| Change::SetWeight {
+            from: edge.from,
+            to: edge.to,
+            weight: Weight::ONE,
+        });
+
+        std::iter::once(Change::SetWeighted(false))
+            .chain(weights)
+            .collect()
+    }
+
+    /// Describes the change, `directed` picks the arrow used for the edges
+    pub fn describe(&self, directed: bool) -> String {
+        let arrow = if directed { "->" } else { "<->" };
+
+        match self {
+            Change::AddNode { node, edges } if edges.is_empty() => {
+                format!("Vértice {} adicionado", node.code)
+            }
+            Change::AddNode { node, edges } => format!(
+                "Vértice {} adicionado com {} aresta(s)",
+                node.code,
+                edges.len()
+            ),
+            Change::RemoveNode(code) => format!("Vértice {code} removido"),
+            Change::RenameNode { code, name } => {
+                format!("Vértice {code} renomeado para {name}")
+            }
+            Change::AddEdge(edge) => format!("Aresta {} {arrow} {} adicionada", edge.from, edge.to),
+            Change::RemoveEdge { from, to } => format!("Aresta {from} {arrow} {to} removida"),
+            Change::SetWeight { from, to, weight } => {
+                format!("Peso da aresta {from} {arrow} {to} alterado para {weight}")
+            }
+            Change::SetWeighted(true) => "Grafo tornado ponderado".to_string(),
+            Change::SetWeighted(false) => "Grafo tornado não ponderado".to_string(),
+            Change::SetNodeAttribute {
+                code,
+                key,
+                value: Some(value),
+            } => format!("Atributo {key} do vértice {code} definido como {value}"),
+            Change::SetNodeAttribute {
+                code,
+                key,
+                value: None,
+            } => format!("Atributo {key} do vértice {code} removido"),
+            Change::SetEdgeAttribute {
+                from,
+                to,
+                key,
+                value: Some(value),
+            } => format!("Atributo {key} da aresta {from} {arrow} {to} definido como {value}"),
+            Change::SetEdgeAttribute {
+                from,
+                to,
+                key,
+                value: None,
+            } => format!("Atributo {key} da aresta {from} {arrow} {to} removido"),
+        }
+    }
+}
+
+/// Changes applied together, undone and redone as one
+#[derive(Debug, Clone)]
+struct Command {
+    description: String,
+    /// Inverses of the applied changes, reverting applies them last to first
+    inverses: Vec<Change>,
+}
+
+/// Undo and redo stacks of the changes made to a graph, and a log of what
+/// happened since it was last saved
+#[derive(Debug, Default)]
+pub struct History {
+    undo: Vec<Command>,
+    redo: Vec<Command>,
+    log: Vec<String>,
+}
+
+impl History {
+    pub fn new() -> History {
+        History::default()
+    }
+
+    /// Applies the change and records it, see `apply_all`
+    pub fn apply(&mut self, graph: &mut Graph, change: Change) -> Result<String, GraphError> {
+        let description = change.describe(graph.is_directed());
+
+        self.apply_all(graph, description, vec![change])
+    }
+
+    /// Applies the changes in order and records them as a single command,
+    /// returns its description. When one fails, the ones already applied are
+    /// reverted and the graph is left as it was
+    pub fn apply_all(
+        &mut self,
+        graph: &mut Graph,
+        description: String,
+        changes: Vec<Change>,
+    ) -> Result<String, GraphError> {
+        let inverses = apply_changes(graph, changes)?;
+
+        self.undo.push(Command {
+            description: description.clone(),
+            inverses,
+        });
+        self.redo.clear();
+        self.log.push(description.clone());
+
+        Ok(description)
+    }
+
+    /// Reverts the last command, returns its description or `None` when
+    /// there's nothing to undo
+    pub fn undo(&mut self, graph: &mut Graph) -> Result<Option<String>, GraphError> {
+        let Some(command) = self.undo.pop() else {
+            return Ok(None);
+        };
+
+        let redo = match revert(graph, &command) {
+            Ok(redo) => redo,
+            Err(err) => {
+                self.undo.push(command);
+                return Err(err);
+            }
+        };
+
+        self.log.push(format!("Desfeito: {}", command.description));
+        self.redo.push(redo);
+
+        Ok(Some(command.description))
+    }
+
+    /// Applies again the last undone command, returns its description or
+    /// `None` when there's nothing to redo
+    pub fn redo(&mut self, graph: &mut Graph) -> Result<Option<String>, GraphError> {
+        let Some(command) = self.redo.pop() else {
+            return Ok(None);
+        };
+
+        let undo = match revert(graph, &command) {
+            Ok(undo) => undo,
+            Err(err) => {
+                self.redo.push(command);
+                return Err(err);
+            }
+        };
+
+        self.log.push(format!("Refeito: {}", command.description));
+        self.undo.push(undo);
+
+        Ok(Some(command.description))
+    }
+
+    pub fn can_undo(&self) -> bool {
+        !self.undo.is_empty()
+    }
+
+    pub fn can_redo(&self) -> bool {
+        !self.redo.is_empty()
+    }
+
+    /// What was applied, undone and redone since the graph was last saved
+    pub fn log(&self) -> &[String] {
+        &self.log
+    }
+
+    /// Clears the log, the changes can still be undone
+    pub fn mark_saved(&mut self) {
+        self.log.clear();
+    }
+
+    /// Forgets everything, used when another graph is loaded
+    pub fn clear(&mut self) {
+        *self = History::new();
+    }
+}
+
+/// Applies the changes, returns their inverses in the same order
+fn apply_changes(graph: &mut Graph, changes: Vec<Change>) -> Result<Vec<Change>, GraphError> {
+    let mut inverses = Vec::new();
+
+    for change in changes {
+        match change.apply(graph) {
+            Ok(inverse) => inverses.push(inverse),
+            Err(err) => {
+                for inverse in inverses.into_iter().rev() {
+                    inverse
+                        .apply(graph)
+                        .expect("the inverse of an applied change can always be applied");
+                }
+
+                return Err(err);
+            }
+        }
+    }
+
+    Ok(inverses)
+}
+
+/// Applies the inverses of the command, last first, and returns the command
+/// that reverts it. Its inverses end up in the opposite order, so they're
+/// applied first to last when it's reverted in turn
+fn revert(graph: &mut Graph, command: &Command) -> Result<Command, GraphError> {
+    let inverses = apply_changes(graph, command.inverses.iter().rev().cloned().collect())?;
+
+    Ok(Command {
+        description: command.description.clone(),
+        inverses,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::fixtures::build;
+
+    fn triangle() -> Graph {
+        build(false, &[1, 2, 3], &[(1, 2, 4), (2, 3, 2), (3, 3, 1)])
+    }
+
+    fn ends(graph: &Graph) -> Vec<(usize, usize)> {
+        graph.edges().map(|edge| (edge.from, edge.to)).collect()
+    }
+
+    #[test]
+    fn undoing_a_node_removal_keeps_the_edge_order() {
+        let mut graph = triangle();
+        let saved = graph.to_json();
+        let mut history = History::new();
+
+        history.apply(&mut graph, Change::RemoveNode(3)).unwrap();
+        assert_eq!(ends(&graph), vec![(1, 2)]);
+
+        history.undo(&mut graph).unwrap();
+        assert_eq!(ends(&graph), vec![(1, 2), (2, 3), (3, 3)]);
+        assert_eq!(graph.to_json(), saved);
+    }
+
+    #[test]
+    fn undo_and_redo_restore_the_graph() {
+        let mut graph = triangle();
+        let before = graph.to_json();
+        let mut history = History::new();
+
+        history
+            .apply(
+                &mut graph,
+                Change::RenameNode {
+                    code: 1,
+                    name: "D".to_string(),
+                },
+            )
+            .unwrap();
+        history
+            .apply(
+                &mut graph,
+                Change::SetEdgeAttribute {
+                    from: 1,
+                    to: 2,
+                    key: "coberta".to_string(),
+                    value: Some("sim".to_string()),
+                },
+            )
+            .unwrap();
+        history
+            .apply(&mut graph, Change::RemoveEdge { from: 1, to: 2 })
+            .unwrap();
+        let after = graph.to_json();
+
+        while history.undo(&mut graph).unwrap().is_some() {}
+        assert_eq!(graph.to_json(), before);
+        assert!(!history.can_undo() && history.can_redo());
+
+        while history.redo(&mut graph).unwrap().is_some() {}
+        assert_eq!(graph.to_json(), after);
+        assert_eq!(graph.find_by_code(1).unwrap().name, "D");
+        assert_eq!(history.log().len(), 9);
+    }
+
+    #[test]
+    fn new_changes_clear_the_redo_stack() {
+        let mut graph = triangle();
+        let mut history = History::new();
+
+        history.apply(&mut graph, Change::RemoveNode(3)).unwrap();
+        history.undo(&mut graph).unwrap();
+        history.apply(&mut graph, Change::RemoveNode(2)).unwrap();
+
+        assert!(!history.can_redo());
+        assert_eq!(history.redo(&mut graph).unwrap(), None);
+    }
+
+    #[test]
+    fn failed_commands_leave_the_graph_as_it_was() {
+        let mut graph = triangle();
+        let before = graph.to_json();
+        let mut history = History::new();
+
+        let changes = vec![
+            Change::RemoveEdge { from: 1, to: 2 },
+            Change::RemoveEdge { from: 1, to: 3 },
+        ];
+
+        assert!(history
+            .apply_all(&mut graph, "Arestas".to_string(), changes)
+            .is_err());
+        assert_eq!(graph.to_json(), before);
+        assert!(!history.can_undo());
+    }
+
+    #[test]
+    fn setting_an_invalid_weight_is_not_recorded() {
+        let mut graph = triangle();
+        let mut history = History::new();
+
+        let change = Change::SetWeight {
+            from: 1,
+            to: 2,
+            weight: Weight::ZERO,
+        };
+
+        assert!(matches!(
+            history.apply(&mut graph, change),
+            Err(GraphError::InvalidWeight(_))
+        ));
+        assert!(!history.can_undo());
+    }
+
+    #[test]
+    fn undoing_a_node_removal_restores_repeated_edges() {
+        // Repeated edges were never rejected when loading
+        let data = r#"{"version": 1, "is_weighted": false, "is_directed": false,
+            "nodes": [
+                {"code": 1, "name": "A", "local_type": ""},
+                {"code": 2, "name": "B", "local_type": ""}
+            ],
+            "edges": [{"from": 1, "to": 2, "weight": 1}, {"from": 2, "to": 1, "weight": 1}]}"#;
+        let mut graph = Graph::from_json(data).unwrap();
+        let saved = graph.to_json();
+        let mut history = History::new();
+
+        history.apply(&mut graph, Change::RemoveNode(2)).unwrap();
+        history.undo(&mut graph).unwrap();
+
+        assert_eq!(graph.to_json(), saved);
+    }
+}
@@ -0,0 +1,25 @@
+//! Graph model used by the n1_project binary: construction, queries,
+//! serialization and export.
+
+pub mod csv;
+pub mod dot_parser;
+pub mod error;
+#[cfg(test)]
+mod fixtures;
+pub mod gexf;
+pub mod graph;
+pub mod graph_exporter;
+pub mod graph_importer;
+pub mod graphml;
+pub mod history;
+pub mod node;
+pub mod schema;
+pub mod svg_renderer;
+pub mod weight;
+mod xml;
+
+pub use error::{CycleErrorKind, GraphError};
+pub use graph::{Edge, Graph, PathMode};
+pub use history::{Change, History};
+pub use node::{Attributes, Node};
+pub use weight::Weight;
@@ -1,47 +1,72 @@
+mod cli;
 mod feedback;
-mod graph;
 mod graph_builder;
-mod graph_exporter;
 mod menu;
-mod node;
 
 use crate::{
     feedback::Feedback,
-    graph::*,
-    menu::{MenuOpt, FILE_PATH},
+    menu::{MenuOpt, DEFAULT_FILE_PATH},
 };
-use std::{fs, path::Path};
+use n1_project::{Graph, History};
+use std::{
+    env, fs,
+    path::{Path, PathBuf},
+    process::ExitCode,
+};
+
+fn main() -> ExitCode {
+    let args: Vec<String> = env::args().skip(1).collect();
 
-const GRAPH_SIZE: usize = 10;
+    let mut file = match args.as_slice() {
+        [] => PathBuf::from(DEFAULT_FILE_PATH),
+        [flag, file] if flag == "-f" || flag == "--file" => PathBuf::from(file),
+        _ => return cli::run(&args),
+    };
 
-fn main() {
-    let mut graph = match setup_graph_menu() {
+    let mut graph = match setup_graph_menu(&file) {
         Some(graph) => graph,
         None => {
             println!("\nEncerrando...");
-            return;
+            return ExitCode::SUCCESS;
         }
     };
 
+    // Graphs imported from other formats are saved as JSON
+    file = menu::save_path(&file);
+    let mut history = History::new();
+    let mut autosave = true;
+
+    menu::write_recovery(&graph, &file, autosave);
+
     loop {
-        menu::show_menu();
+        menu::show_menu(&graph, &file, autosave);
 
         let option = menu::read_option();
 
         if option == MenuOpt::Exit {
+            if !menu::confirm_discard_changes(&graph, &file) {
+                continue;
+            }
+
             println!("\nEncerrando...");
             break;
         }
 
-        print!("\n");
+        println!();
 
-        menu::run_option(option, &mut graph);
+        menu::run_option(option, &mut graph, &mut file, &mut history, &mut autosave);
     }
+
+    ExitCode::SUCCESS
 }
 
-fn setup_graph_menu() -> Option<Graph> {
+fn setup_graph_menu(file: &Path) -> Option<Graph> {
+    if let Some(graph) = menu::restore_recovery(&menu::save_path(file)) {
+        return Some(graph);
+    }
+
     loop {
-        let has_data = match fs::read_to_string(Path::new(FILE_PATH)) {
+        let has_data = match fs::read_to_string(file) {
             Ok(data) => !data.is_empty(),
             Err(_) => false,
         };
@@ -50,15 +75,15 @@ fn setup_graph_menu() -> Option<Graph> {
             return Some(graph_builder::init_graph());
         }
 
-        menu::show_menu_load_graph();
+        menu::show_menu_load_graph(file);
 
         return match menu::read_option() {
-            MenuOpt::Save => match menu::load_graph() {
-                None => {
-                    println!("{}", Feedback::read_graph_file_error());
+            MenuOpt::Save => match menu::load_graph(file) {
+                Ok(graph) => Some(graph),
+                Err(err) => {
+                    println!("{}", Feedback::read_graph_file_error(&err));
                     continue;
                 }
-                graph => graph,
             },
             MenuOpt::No => Some(graph_builder::init_graph()),
             MenuOpt::Exit => {
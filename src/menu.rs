@@ -1,17 +1,22 @@
-use crate::{
-    feedback::Feedback,
-    graph::{Edge, Graph},
-    graph_builder,
-    graph_exporter::{self, DOT_OUTPUT},
-    node::Node,
-};
+use crate::{feedback::Feedback, graph_builder};
 use colored::Colorize;
-use std::{env, fs, io, path::Path};
+use n1_project::{
+    csv,
+    graph_exporter::{self, Backend, DotFormat, DotOptions},
+    graph_importer,
+    svg_renderer::Layout,
+    Attributes, Change, Edge, Graph, GraphError, History, Node, PathMode, Weight,
+};
+use std::{
+    env, fs, io,
+    path::{Path, PathBuf},
+    str::FromStr,
+};
 use MenuOpt::*;
 
 type RunOptResult = Result<String, String>;
 
-pub const FILE_PATH: &str = "./graph.json";
+pub const DEFAULT_FILE_PATH: &str = "./graph.json";
 
 #[derive(PartialEq)]
 pub enum MenuOpt {
@@ -25,15 +30,62 @@ pub enum MenuOpt {
     H,
     I,
     J,
+    K,
+    M,
+    O,
+    P,
+    R,
+    U,
+    Z,
     No,
     Load,
     Visualize,
     Save,
+    SaveAs,
     Export,
+    Csv,
+    Autosave,
     Exit,
 }
 
-pub fn show_menu_load_graph() {
+enum CsvMode {
+    ImportLists,
+    ImportMatrix,
+    ExportLists,
+    ExportMatrix,
+}
+
+enum AttributeMode {
+    Node,
+    Edge,
+    Query,
+}
+
+enum WeightMode {
+    Edit,
+    Scale,
+    Remove,
+}
+
+enum HistoryMode {
+    Undo,
+    Redo,
+    Log,
+}
+
+enum NodeMode {
+    Add,
+    Rename,
+    Remove,
+}
+
+enum CycleMode {
+    All,
+    Through,
+    Verify,
+}
+
+pub fn show_menu_load_graph(file: &Path) {
     println!(
         "\n{}",
         "-------------------------------------------------------------------------------".magenta()
@@ -41,7 +93,11 @@ pub fn show_menu_load_graph() {
     println!("{}", "** Menu **".blue().bold());
     println!(
         "{}",
-        "Detectamos um grafo existente, desja carrega-lo?".yellow()
+        format!(
+            "Detectamos um grafo existente em {}, desja carrega-lo?",
+            file.display()
+        )
+        .yellow()
     );
 
     println!(
@@ -60,29 +116,41 @@ pub fn show_menu_load_graph() {
     );
 }
 
-pub fn show_menu() {
+pub fn show_menu(graph: &Graph, file: &Path, autosave: bool) {
     println!(
         "\n{}",
         "-------------------------------------------------------------------------------".magenta()
     );
     println!("{}", "** Menu **".blue().bold());
+    println!("{}", Feedback::current_file(file, graph.is_dirty()));
 
     println!(
         "\
 {}) Verificar se dois vértices informados são adjacentes
 {}) Verificar se existe um laço a partir da leitura de um vértice
 {}) Verificar se existe e exibir o caminho a partir da leitura de dois vértices
-{}) Verificar se existe e exibir o comprimento dado um ciclo
+{}) Detectar e exibir os ciclos do grafo
 {}) Criar novas arestas
 {}) Remover arestas
-{}) Tornar o grafo ponderado
+{}) Tornar o grafo ponderado ou editar os pesos das arestas
 {}) Verificar se um dado grafo é subgrafo
 {}) Verificar se o grafo é completo
 {}) Calcular o custo do caminho entre dois vértices informados
+{}) Listar todos os caminhos entre dois vértices informados
+{}) Calcular a árvore geradora mínima
+{}) Exibir os componentes conexos do grafo
+{}) Exibir as pontes e os pontos de articulação do grafo
+{}) Adicionar, renomear ou remover vértices
+{}) Consultar e editar atributos dos vértices e arestas
+{}) Desfazer, refazer ou listar as alterações
 ---
 {}) Visualizar grafo
+{}) Abrir outro grafo
 {}) Salvar grafo
-{}) Exportar grafo como PNG
+{}) Salvar grafo como
+{}) Exportar grafo (SVG, PNG ou PDF)
+{}) Importar ou exportar CSV (vértices e arestas, matriz de adjacência)
+{}) {} o salvamento automático
 {}) Encerrar",
         "a".magenta().bold(),
         "b".magenta().bold(),
@@ -94,9 +162,21 @@ pub fn show_menu() {
         "h".magenta().bold(),
         "i".magenta().bold(),
         "j".magenta().bold(),
+        "k".magenta().bold(),
+        "m".magenta().bold(),
+        "o".magenta().bold(),
+        "p".magenta().bold(),
+        "r".magenta().bold(),
+        "u".magenta().bold(),
+        "z".magenta().bold(),
         "v".magenta().bold(),
+        "l".magenta().bold(),
         "s".magenta().bold(),
+        "w".magenta().bold(),
         "x".magenta().bold(),
+        "t".magenta().bold(),
+        "y".magenta().bold(),
+        if autosave { "Desativar" } else { "Ativar" },
         "q".magenta().bold(),
     );
 
@@ -114,7 +194,7 @@ pub fn read_option() -> MenuOpt {
 
         io::stdin().read_line(&mut option).unwrap();
 
-        match parse_option(&option.trim()) {
+        match parse_option(option.trim()) {
             Some(opt) => {
                 // Clear input
                 print!("{}", Feedback::value_read(&option, "Opção digitada"));
@@ -140,38 +220,73 @@ fn parse_option(option: &str) -> Option<MenuOpt> {
         "h" => Some(H),
         "i" => Some(I),
         "j" => Some(J),
+        "k" => Some(K),
+        "m" => Some(M),
+        "o" => Some(O),
+        "p" => Some(P),
+        "r" => Some(R),
+        "u" => Some(U),
+        "z" => Some(Z),
         "n" => Some(No),
         "l" => Some(Load),
         "v" => Some(Visualize),
         "s" => Some(Save),
+        "w" => Some(SaveAs),
         "x" => Some(Export),
+        "t" => Some(Csv),
+        "y" => Some(Autosave),
         "q" => Some(Exit),
         _ => None,
     }
 }
 
-pub fn run_option(option: MenuOpt, graph: &mut Graph) {
+pub fn run_option(
+    option: MenuOpt,
+    graph: &mut Graph,
+    file: &mut PathBuf,
+    history: &mut History,
+    autosave: &mut bool,
+) {
+    // Opening or importing a graph leaves the recovery file of the new one
+    // alone, it was already offered and is only removed once saved or discarded
+    let changes_graph = matches!(option, E | F | G | R | U | Z | Save | SaveAs);
+
     let result = match option {
         A => verify_if_two_nodes_are_adjacent(graph),
         B => has_buckle_menu(graph),
-        C => find_path_menu(graph),
-        D => find_and_show_cycle(graph),
-        E => add_edges_menu(graph),
-        F => remove_edge_menu(graph),
-        G => make_graph_weighted(graph),
+        C => find_path_menu(graph, file),
+        D => find_and_show_cycle(graph, file),
+        E => add_edges_menu(graph, history),
+        F => remove_edge_menu(graph, history),
+        G => weights_menu(graph, history),
         H => verify_if_graph_contains_subgraph(graph),
         I => is_graph_complete(graph),
-        J => calc_path_between_nodes(graph),
-        Save => save_graph(graph),
+        J => calc_path_between_nodes(graph, file),
+        K => list_all_paths_menu(graph),
+        M => minimum_spanning_tree_menu(graph, file),
+        O => connected_components_menu(graph),
+        P => bridges_menu(graph),
+        R => manage_nodes_menu(graph, history),
+        U => attributes_menu(graph, history),
+        Z => history_menu(graph, history),
+        Load => open_graph_menu(graph, file, history),
+        Save => save_graph(graph, file, history),
+        SaveAs => save_graph_as_menu(graph, file, history),
+        Csv => csv_menu(graph, file, history),
         Visualize => show_graph(graph),
-        Export => export_graph(graph),
-        _ => Ok(format!("i")),
+        Export => export_graph(graph, None, file),
+        Autosave => toggle_autosave(graph, file, autosave),
+        _ => Ok("i".to_string()),
     };
 
     match result {
         Ok(success) => println!("{success}"),
         Err(err) => println!("{err}"),
     }
+
+    if changes_graph {
+        write_recovery(graph, file, *autosave);
+    }
 }
 
 fn verify_if_two_nodes_are_adjacent(graph: &Graph) -> RunOptResult {
@@ -197,54 +312,118 @@ fn has_buckle_menu(graph: &mut Graph) -> RunOptResult {
 
     let node = read_node(graph)?;
 
-    if graph.has_buckle(&node) {
+    if graph.has_buckle(node) {
         Ok(Feedback::contains_buckle(node.code))
     } else {
         Ok(Feedback::no_buckle(node.code))
     }
 }
 
-fn find_path_menu(graph: &mut Graph) -> RunOptResult {
+fn find_path_menu(graph: &Graph, file: &Path) -> RunOptResult {
     println!("{}", Feedback::nth_node("Primeiro"));
     let node1 = read_node(graph)?;
 
     println!("\n{}", Feedback::nth_node("Segundo"));
     let node2 = read_node(graph)?;
 
-    print!("\n");
+    println!();
+
+    let mode = read_path_mode();
+
+    println!();
 
-    match graph.get_path(node1, node2) {
-        Some(path) => {
+    match graph.get_path(node1, node2, mode) {
+        Ok(path) => {
             println!("{}", Feedback::path_found());
-            Ok(get_string_path(path))
+            println!("{}\n", get_string_path(path.clone(), graph.is_directed()));
+
+            offer_highlighted_export(graph, &graph.path_subgraph(&path), file, "o caminho")
         }
-        None => return Ok(Feedback::no_path_found(node1.code, node2.code)),
+        Err(err) => Err(Feedback::graph_error(&err)),
     }
 }
 
-fn find_and_show_cycle(graph: &Graph) -> RunOptResult {
-    let codes = read_cycle();
-    print!("\n");
+fn find_and_show_cycle(graph: &Graph, file: &Path) -> RunOptResult {
+    if graph.is_acyclic() {
+        return Ok(Feedback::graph_is_acyclic());
+    }
+
+    println!("{}\n", Feedback::graph_is_not_acyclic());
+
+    let cycles = match read_cycle_mode() {
+        CycleMode::All => graph.find_cycles(),
+        CycleMode::Through => {
+            println!("{}\n", format_available_nodes(graph));
+            let node = read_node(graph)?;
 
-    match graph.get_cycle(&codes) {
-        Some(cycle) => {
-            println!("{}", Feedback::cycle_found());
-            Ok(get_string_path(cycle))
+            graph
+                .cycles_through(node)
+                .map_err(|err| Feedback::graph_error(&err))?
         }
-        None => Ok(Feedback::no_cycle_found()),
+        CycleMode::Verify => {
+            let codes = read_cycle();
+            println!();
+
+            return match graph.get_cycle(&codes) {
+                Ok(cycle) => {
+                    println!("{}", Feedback::cycle_found());
+
+                    let highlight = graph.path_subgraph(&cycle);
+                    println!("{}\n", format_cycle(graph, cycle)?);
+
+                    offer_highlighted_export(graph, &highlight, file, "o ciclo")
+                }
+                Err(err) => Err(Feedback::graph_error(&err)),
+            };
+        }
+    };
+
+    println!();
+
+    if cycles.is_empty() {
+        return Ok(Feedback::no_cycle_found());
+    }
+
+    println!("{}", Feedback::cycles_found(cycles.len()));
+
+    let result = cycles
+        .into_iter()
+        .enumerate()
+        .map(|(i, cycle)| Ok(format!("{}. {}", i + 1, format_cycle(graph, cycle)?)))
+        .collect::<Result<Vec<_>, String>>()?
+        .join("\n");
+
+    Ok(result)
+}
+
+fn format_cycle(graph: &Graph, cycle: Vec<&Node>) -> RunOptResult {
+    if !graph.is_weighted() {
+        return Ok(get_string_path(cycle, graph.is_directed()));
     }
+
+    let weight = graph
+        .cycle_weight(&cycle)
+        .map_err(|err| Feedback::graph_error(&err))?;
+
+    Ok(format!(
+        "{} {}",
+        Feedback::path_cost(weight),
+        get_string_path(cycle, graph.is_directed())
+    ))
 }
 
-fn get_string_path(nodes: Vec<&Node>) -> String {
+pub fn get_string_path(nodes: Vec<&Node>, directed: bool) -> String {
+    let separator = if directed { " -> " } else { " <-> " };
+
     nodes
         .iter()
-        .map(|x| format!("[{}] {}", x.code.to_string(), x.name))
+        .map(|x| format!("[{}] {}", x.code, x.name))
         .collect::<Vec<_>>()
-        .join(" <-> ")
+        .join(separator)
 }
 
-fn add_edges_menu(graph: &mut Graph) -> RunOptResult {
-    let default_weight = 1;
+fn add_edges_menu(graph: &mut Graph, history: &mut History) -> RunOptResult {
+    let default_weight = Weight::ONE;
 
     println!("{}\n", format_available_nodes(graph));
 
@@ -260,8 +439,11 @@ fn add_edges_menu(graph: &mut Graph) -> RunOptResult {
     let to = read_codes(graph);
 
     for code in to {
-        let weight = if graph.is_weighted {
-            println!("Aresta {}", Feedback::format_edge(from, code).blue());
+        let weight = if graph.is_weighted() {
+            println!(
+                "Aresta {}",
+                Feedback::format_edge(from, code, graph.is_directed()).blue()
+            );
             read_weight()
         } else {
             default_weight
@@ -271,11 +453,14 @@ fn add_edges_menu(graph: &mut Graph) -> RunOptResult {
             from,
             to: code,
             weight,
+            attributes: Attributes::new(),
         };
 
-        match graph.add_edge(edge.clone()) {
-            Ok(_) => println!("{}\n", Feedback::edge_added(edge)),
-            Err(_) => println!("{}\n", Feedback::edge_already_exists(&edge)),
+        let is_directed = graph.is_directed();
+
+        match history.apply(graph, Change::AddEdge(edge.clone())) {
+            Ok(_) => println!("{}\n", Feedback::edge_added(edge, is_directed)),
+            Err(err) => println!("{}\n", Feedback::graph_error(&err)),
         };
     }
 
@@ -284,7 +469,7 @@ fn add_edges_menu(graph: &mut Graph) -> RunOptResult {
     Ok("".to_string())
 }
 
-fn remove_edge_menu(graph: &mut Graph) -> RunOptResult {
+fn remove_edge_menu(graph: &mut Graph, history: &mut History) -> RunOptResult {
     println!("{}\n", format_available_nodes(graph));
 
     println!("{}", Feedback::nth_node("Primeiro"));
@@ -293,35 +478,280 @@ fn remove_edge_menu(graph: &mut Graph) -> RunOptResult {
     println!("\n{}", Feedback::nth_node("Segundo"));
     let to = read_node(graph)?.code;
 
-    print!("\n");
-    match graph.remove_edge(from, to) {
-        Ok(_) => Ok(Feedback::edge_removed(from, to)),
-        Err(_) => Err(Feedback::edge_dont_exists()),
+    println!();
+
+    if graph.find_edge_by_from_to(from, to).is_some() && graph.is_bridge(from, to) {
+        println!(
+            "{}",
+            Feedback::edge_is_bridge(from, to, graph.is_directed())
+        );
+
+        if !read_confirmation(&Feedback::confirm_remove_bridge()) {
+            return Ok(Feedback::edge_not_removed());
+        }
+
+        println!();
+    }
+
+    match history.apply(graph, Change::RemoveEdge { from, to }) {
+        Ok(_) => Ok(Feedback::edge_removed(from, to, graph.is_directed())),
+        Err(err) => Err(Feedback::graph_error(&err)),
+    }
+}
+
+fn manage_nodes_menu(graph: &mut Graph, history: &mut History) -> RunOptResult {
+    let result = match read_node_mode() {
+        NodeMode::Add => {
+            let node = match graph_builder::read_new_node(graph, true) {
+                Some(node) => node,
+                None => return Ok("".to_string()),
+            };
+            let code = node.code;
+            let change = Change::AddNode {
+                node,
+                edges: Vec::new(),
+            };
+
+            match history.apply(graph, change) {
+                Ok(_) => Feedback::node_added(code),
+                Err(err) => return Err(Feedback::graph_error(&err)),
+            }
+        }
+        NodeMode::Rename => {
+            println!("{}\n", format_available_nodes(graph));
+            let code = read_node(graph)?.code;
+            let name = graph_builder::read_node_name();
+
+            match history.apply(graph, Change::RenameNode { code, name }) {
+                Ok(_) => Feedback::node_renamed(code),
+                Err(err) => return Err(Feedback::graph_error(&err)),
+            }
+        }
+        NodeMode::Remove => {
+            // Without nodes there would be no valid code for `read_node`
+            if graph.size() <= 1 {
+                return Err(Feedback::last_node_not_removed());
+            }
+
+            println!("{}\n", format_available_nodes(graph));
+            let code = read_node(graph)?.code;
+            let incident_edges = graph
+                .edges()
+                .filter(|edge| edge.from == code || edge.to == code)
+                .count();
+
+            match history.apply(graph, Change::RemoveNode(code)) {
+                Ok(_) => Feedback::node_removed(code, incident_edges),
+                Err(err) => return Err(Feedback::graph_error(&err)),
+            }
+        }
+    };
+
+    Ok(format!(
+        "\n{result}\n{}",
+        Feedback::graph_size(graph.size())
+    ))
+}
+
+fn attributes_menu(graph: &mut Graph, history: &mut History) -> RunOptResult {
+    match read_attribute_mode() {
+        AttributeMode::Node => {
+            println!("{}\n", format_available_nodes(graph));
+            let node = read_node(graph)?;
+            let code = node.code;
+
+            println!("{}\n", Feedback::current_attributes(&node.attributes));
+
+            let key = read_attribute_key(&Feedback::read_attribute_key());
+            let is_set = node.attributes.contains_key(&key);
+            let value = read_attribute_value();
+
+            let result = match &value {
+                Some(value) => Feedback::attribute_set(&key, value),
+                None if is_set => Feedback::attribute_removed(&key),
+                None => return Err(Feedback::attribute_not_set(&key)),
+            };
+
+            history
+                .apply(graph, Change::SetNodeAttribute { code, key, value })
+                .map(|_| result)
+                .map_err(|err| Feedback::graph_error(&err))
+        }
+        AttributeMode::Edge => {
+            println!("{}\n", format_available_nodes(graph));
+
+            println!("{}", Feedback::nth_node("Primeiro"));
+            let from = read_node(graph)?.code;
+
+            println!("\n{}", Feedback::nth_node("Segundo"));
+            let to = read_node(graph)?.code;
+
+            let edge = graph
+                .find_edge_by_from_to(from, to)
+                .ok_or_else(|| Feedback::graph_error(&GraphError::EdgeNotFound { from, to }))?;
+
+            println!("\n{}\n", Feedback::current_attributes(&edge.attributes));
+
+            let key = read_attribute_key(&Feedback::read_attribute_key());
+            let is_set = edge.attributes.contains_key(&key);
+            let value = read_attribute_value();
+
+            let result = match &value {
+                Some(value) => Feedback::attribute_set(&key, value),
+                None if is_set => Feedback::attribute_removed(&key),
+                None => return Err(Feedback::attribute_not_set(&key)),
+            };
+
+            let change = Change::SetEdgeAttribute {
+                from,
+                to,
+                key,
+                value,
+            };
+
+            history
+                .apply(graph, change)
+                .map(|_| result)
+                .map_err(|err| Feedback::graph_error(&err))
+        }
+        AttributeMode::Query => {
+            let keys = graph.attribute_keys();
+
+            if keys.is_empty() {
+                return Err(Feedback::no_attributes());
+            }
+
+            println!("{}\n", Feedback::available_attributes(&keys));
+
+            // `chave` lists everything with the attribute, `chave=valor` filters by value
+            let query = read_attribute_key(&Feedback::read_attribute_query());
+            let (key, value) = match query.split_once('=') {
+                Some((key, value)) => (key.trim(), Some(value.trim())),
+                None => (query.as_str(), None),
+            };
+
+            let nodes: Vec<String> = graph
+                .nodes_with_attribute(key, value)
+                .map(Feedback::format_node)
+                .collect();
+            let edges: Vec<String> = graph
+                .edges_with_attribute(key, value)
+                .map(|edge| Feedback::format_graph_edge(graph, edge))
+                .collect();
+
+            Ok(Feedback::attribute_query_result(&nodes, &edges))
+        }
     }
 }
 
-fn make_graph_weighted(graph: &mut Graph) -> RunOptResult {
-    if graph.is_weighted {
-        return Err(Feedback::graph_already_weighted());
+fn history_menu(graph: &mut Graph, history: &mut History) -> RunOptResult {
+    match read_history_mode() {
+        HistoryMode::Undo => match history.undo(graph) {
+            Ok(Some(description)) => Ok(Feedback::change_undone(&description)),
+            Ok(None) => Err(Feedback::nothing_to_undo()),
+            Err(err) => Err(Feedback::graph_error(&err)),
+        },
+        HistoryMode::Redo => match history.redo(graph) {
+            Ok(Some(description)) => Ok(Feedback::change_redone(&description)),
+            Ok(None) => Err(Feedback::nothing_to_redo()),
+            Err(err) => Err(Feedback::graph_error(&err)),
+        },
+        HistoryMode::Log => Ok(Feedback::changes_log(history.log())),
     }
+}
 
-    graph.make_weighted();
+/// Makes the graph weighted, once it is the weights can be edited
+fn weights_menu(graph: &mut Graph, history: &mut History) -> RunOptResult {
+    if !graph.is_weighted() {
+        return make_graph_weighted(graph, history);
+    }
 
-    for edge in &mut graph.edges {
-        println!("Aresta {}", Feedback::format_edge(edge.from, edge.to));
+    match read_weight_mode() {
+        WeightMode::Edit => edit_weight_menu(graph, history),
+        WeightMode::Scale => {
+            let factor = read_scale_factor();
+            let changes =
+                Change::scale_weights(graph, factor).map_err(|err| Feedback::graph_error(&err))?;
+            let description = format!("Pesos das arestas multiplicados por {factor}");
+
+            match history.apply_all(graph, description, changes) {
+                Ok(_) => Ok(Feedback::weights_scaled(factor)),
+                Err(err) => Err(Feedback::graph_error(&err)),
+            }
+        }
+        WeightMode::Remove => {
+            let changes = Change::remove_weights(graph);
+            let description = Change::SetWeighted(false).describe(graph.is_directed());
 
-        let weight = read_weight();
+            match history.apply_all(graph, description, changes) {
+                Ok(_) => Ok(Feedback::success_graph_unweighted()),
+                Err(err) => Err(Feedback::graph_error(&err)),
+            }
+        }
+    }
+}
 
-        Graph::add_weight(edge, weight);
+fn edit_weight_menu(graph: &mut Graph, history: &mut History) -> RunOptResult {
+    println!("{}\n", format_available_nodes(graph));
 
-        print!("\n")
+    println!("{}", Feedback::nth_node("Primeiro"));
+    let from = read_node(graph)?.code;
+
+    println!("\n{}", Feedback::nth_node("Segundo"));
+    let to = read_node(graph)?.code;
+
+    println!();
+
+    let edge = graph
+        .find_edge_by_from_to(from, to)
+        .ok_or_else(|| Feedback::graph_error(&GraphError::EdgeNotFound { from, to }))?;
+
+    println!("{}", Feedback::format_graph_edge(graph, edge));
+
+    let weight = read_weight();
+
+    println!();
+
+    match history.apply(graph, Change::SetWeight { from, to, weight }) {
+        Ok(_) => Ok(Feedback::weight_changed(
+            from,
+            to,
+            weight,
+            graph.is_directed(),
+        )),
+        Err(err) => Err(Feedback::graph_error(&err)),
     }
+}
+
+fn make_graph_weighted(graph: &mut Graph, history: &mut History) -> RunOptResult {
+    // Undone in a single step, along with the weights
+    let mut changes = vec![Change::SetWeighted(true)];
 
-    Ok(Feedback::success_graph_weighted())
+    for edge in graph.edges() {
+        println!(
+            "Aresta {}",
+            Feedback::format_edge(edge.from, edge.to, graph.is_directed())
+        );
+
+        changes.push(Change::SetWeight {
+            from: edge.from,
+            to: edge.to,
+            weight: read_weight(),
+        });
+
+        println!()
+    }
+
+    let description = Change::SetWeighted(true).describe(graph.is_directed());
+
+    match history.apply_all(graph, description, changes) {
+        Ok(_) => Ok(Feedback::success_graph_weighted()),
+        Err(err) => Err(Feedback::graph_error(&err)),
+    }
 }
 
 fn verify_if_graph_contains_subgraph(graph: &Graph) -> RunOptResult {
-    let subgraph = graph_builder::read_subgraph(graph.is_weighted);
+    let subgraph = graph_builder::read_subgraph(graph.is_weighted(), graph.is_directed());
 
     if graph.is_subgraph(&subgraph) {
         Ok(Feedback::is_subgraph())
@@ -338,8 +768,8 @@ fn is_graph_complete(graph: &Graph) -> RunOptResult {
     }
 }
 
-fn calc_path_between_nodes(graph: &Graph) -> RunOptResult {
-    if !graph.is_weighted {
+fn calc_path_between_nodes(graph: &Graph, file: &Path) -> RunOptResult {
+    if !graph.is_weighted() {
         return Err(Feedback::graph_is_not_weighted());
     }
 
@@ -349,51 +779,446 @@ fn calc_path_between_nodes(graph: &Graph) -> RunOptResult {
     println!("\n{}", Feedback::nth_node("Segundo"));
     let node2 = read_node(graph)?;
 
-    print!("\n");
+    println!();
 
     match graph.calculate_path(node1, node2) {
-        Some(path_size) => Ok(Feedback::path_size(path_size)),
-        None => Err(Feedback::no_path_found(node1.code, node2.code)),
+        Ok((path_size, path)) => {
+            println!("{}", Feedback::shortest_path_found());
+            println!("{}", get_string_path(path.clone(), graph.is_directed()));
+            println!("{}\n", Feedback::path_size(path_size));
+
+            offer_highlighted_export(graph, &graph.path_subgraph(&path), file, "o caminho")
+        }
+        Err(err) => Err(Feedback::graph_error(&err)),
+    }
+}
+
+fn list_all_paths_menu(graph: &Graph) -> RunOptResult {
+    println!("{}", Feedback::nth_node("Primeiro"));
+    let node1 = read_node(graph)?;
+
+    println!("\n{}", Feedback::nth_node("Segundo"));
+    let node2 = read_node(graph)?;
+
+    println!();
+
+    let max_length = read_limit(&Feedback::read_max_length());
+    let max_weight = if graph.is_weighted() {
+        read_limit(&Feedback::read_max_weight())
+    } else {
+        None
+    };
+
+    println!();
+
+    let paths = graph
+        .get_all_paths(node1, node2, max_length, max_weight)
+        .map_err(|err| Feedback::graph_error(&err))?;
+
+    if paths.is_empty() {
+        return Ok(Feedback::no_path_found(node1.code, node2.code));
+    }
+
+    println!("{}", Feedback::paths_found(paths.len()));
+
+    let result = paths
+        .into_iter()
+        .enumerate()
+        .map(|(i, (cost, path))| {
+            format!(
+                "{}. {} {}",
+                i + 1,
+                Feedback::path_cost(cost),
+                get_string_path(path, graph.is_directed())
+            )
+        })
+        .collect::<Vec<_>>()
+        .join("\n");
+
+    Ok(result)
+}
+
+fn minimum_spanning_tree_menu(graph: &Graph, file: &Path) -> RunOptResult {
+    if !graph.is_weighted() {
+        return Err(Feedback::graph_is_not_weighted());
+    }
+
+    let (tree, cost) = graph
+        .minimum_spanning_tree()
+        .map_err(|err| Feedback::graph_error(&err))?;
+
+    println!("{}", Feedback::minimum_spanning_tree_found());
+    println!("{}\n", Feedback::format_graph(&tree));
+    println!("{}\n", Feedback::minimum_spanning_tree_cost(cost));
+
+    offer_highlighted_export(graph, &tree, file, "a árvore")
+}
+
+fn connected_components_menu(graph: &Graph) -> RunOptResult {
+    let components = graph.connected_components();
+
+    if graph.is_connected() {
+        println!("{}\n", Feedback::graph_is_connected());
+    } else {
+        println!("{}\n", Feedback::graph_is_not_connected(components.len()));
+    }
+
+    let result = components
+        .iter()
+        .enumerate()
+        .map(|(i, codes)| {
+            let nodes = codes
+                .iter()
+                .map(|&code| Feedback::format_node(graph.find_by_code(code).unwrap()))
+                .collect::<Vec<_>>()
+                .join("\n");
+
+            format!("{}\n{nodes}", Feedback::component_title(i + 1))
+        })
+        .collect::<Vec<_>>()
+        .join("\n\n");
+
+    Ok(result)
+}
+
+fn bridges_menu(graph: &Graph) -> RunOptResult {
+    let bridges = graph.bridges();
+    let points = graph.articulation_points();
+
+    let mut result = Feedback::bridges_title();
+
+    if bridges.is_empty() {
+        result = format!("{result}\n{}", Feedback::no_bridges());
+    }
+
+    for edge in bridges {
+        result = format!(
+            "{result}\n{}",
+            Feedback::format_edge(edge.from, edge.to, graph.is_directed())
+        );
+    }
+
+    result = format!("{result}\n\n{}", Feedback::articulation_points_title());
+
+    if points.is_empty() {
+        result = format!("{result}\n{}", Feedback::no_articulation_points());
+    }
+
+    for node in points {
+        result = format!("{result}\n{}", Feedback::format_node(node));
+    }
+
+    Ok(result)
+}
+
+fn save_graph(graph: &mut Graph, file: &Path, history: &mut History) -> RunOptResult {
+    match graph_exporter::write_graph(graph, file) {
+        Ok(_) => {
+            graph.mark_clean();
+            history.mark_saved();
+            Ok(Feedback::save_graph_success(file))
+        }
+        Err(err) => Err(Feedback::save_graph_error(&err)),
+    }
+}
+
+fn save_graph_as_menu(
+    graph: &mut Graph,
+    file: &mut PathBuf,
+    history: &mut History,
+) -> RunOptResult {
+    let new_file = read_file_path();
+
+    if !graph_exporter::can_write(&new_file) {
+        return Err(Feedback::save_format_not_supported());
+    }
+
+    if new_file.exists() && !read_confirmation(&Feedback::confirm_overwrite_file(&new_file)) {
+        return Ok(Feedback::graph_not_saved());
+    }
+
+    let result = save_graph(graph, &new_file, history)?;
+    // The changes are saved, so the recovery of the previous file is stale
+    remove_recovery(file);
+    *file = new_file;
+
+    Ok(result)
+}
+
+/// Replaces the current graph with the one saved in the file read from the
+/// user, or with its autosaved changes when they're restored. The current
+/// graph is kept if it can't be loaded
+fn open_graph_menu(graph: &mut Graph, file: &mut PathBuf, history: &mut History) -> RunOptResult {
+    if !confirm_discard_changes(graph, file) {
+        return Ok(Feedback::graph_not_replaced());
+    }
+
+    let new_file = read_file_path();
+
+    if let Some(recovered) = restore_recovery(&save_path(&new_file)) {
+        *graph = recovered;
+        *file = save_path(&new_file);
+        history.clear();
+
+        return Ok("".to_string());
+    }
+
+    match load_graph(&new_file) {
+        Ok(new_graph) => {
+            *graph = new_graph;
+            history.clear();
+
+            if save_path(&new_file) != new_file {
+                *file = read_import_save_path(save_path(&new_file));
+
+                return Ok(Feedback::graph_imported(file));
+            }
+
+            *file = new_file;
+
+            Ok("".to_string())
+        }
+        Err(err) => Err(Feedback::read_graph_file_error(&err)),
     }
 }
 
-fn save_graph(graph: &Graph) -> RunOptResult {
-    let data = serde_json::to_string(graph).unwrap();
+/// Loads the graph from JSON or imports it from the other supported formats
+/// Where a graph loaded from `file` is saved, graphs imported from formats
+/// that can't be written are saved as JSON next to the original file
+pub fn save_path(file: &Path) -> PathBuf {
+    if graph_exporter::can_write(file) {
+        file.to_path_buf()
+    } else {
+        file.with_extension("json")
+    }
+}
+
+pub fn load_graph(file: &Path) -> Result<Graph, GraphError> {
+    let mut graph = graph_importer::import_graph(file)?;
 
-    match fs::write(Path::new(FILE_PATH), data) {
-        Ok(_) => Ok(Feedback::save_graph_success()),
-        Err(_) => Err(Feedback::save_graph_error()),
+    // Imported graphs only differ from their file when it's saved elsewhere
+    if save_path(file) == file {
+        graph.mark_clean();
     }
+
+    println!("\n{}", Feedback::load_graph_success());
+
+    Ok(graph)
 }
 
-pub fn load_graph() -> Option<Graph> {
-    let data = match fs::read_to_string(Path::new(FILE_PATH)) {
-        Ok(data) => data,
-        Err(_) => return None,
+fn csv_menu(graph: &mut Graph, file: &mut PathBuf, history: &mut History) -> RunOptResult {
+    let mode = read_csv_mode();
+
+    let imports = matches!(mode, CsvMode::ImportLists | CsvMode::ImportMatrix);
+
+    if imports && !confirm_discard_changes(graph, file) {
+        return Ok(Feedback::graph_not_replaced());
+    }
+
+    let result = match mode {
+        CsvMode::ImportLists => import_csv_lists(graph, file).inspect(|_| history.clear()),
+        CsvMode::ImportMatrix => import_adjacency_matrix(graph, file).inspect(|_| history.clear()),
+        CsvMode::ExportLists => {
+            let (nodes_path, edges_path) = csv::list_paths(file);
+
+            fs::write(&nodes_path, csv::nodes_to_csv(graph))
+                .and_then(|_| fs::write(&edges_path, csv::edges_to_csv(graph)))
+                .map(|_| Feedback::csv_exported(&[&nodes_path, &edges_path]))
+                .map_err(GraphError::from)
+        }
+        CsvMode::ExportMatrix => {
+            let matrix_path = csv::matrix_path(file);
+
+            fs::write(&matrix_path, csv::to_adjacency_matrix(graph))
+                .map(|_| Feedback::csv_exported(&[&matrix_path]))
+                .map_err(GraphError::from)
+        }
+    };
+
+    result.map_err(|err| Feedback::graph_error(&err))
+}
+
+/// Replaces the current graph with the one in the nodes and edges CSVs, it
+/// is saved as JSON next to the nodes file
+fn import_csv_lists(graph: &mut Graph, file: &mut PathBuf) -> Result<String, GraphError> {
+    println!("{}", Feedback::read_nodes_csv());
+    let nodes_path = read_path("csv", &["csv"], false).unwrap();
+
+    println!("\n{}", Feedback::read_edges_csv());
+    let edges_path = read_path("csv", &["csv"], true);
+
+    let is_directed = read_confirmation(&Feedback::read_is_directed());
+
+    let nodes = fs::read_to_string(&nodes_path)?;
+    let edges = match &edges_path {
+        Some(edges_path) => Some(fs::read_to_string(edges_path)?),
+        None => None,
     };
 
-    match serde_json::from_str(&data) {
-        Ok(parsed_graph) => {
-            println!("\n{}", Feedback::load_graph_success());
-            parsed_graph
+    *graph = csv::from_csv(&nodes, edges.as_deref(), is_directed)?;
+    *file = read_import_save_path(nodes_path.with_extension("json"));
+
+    Ok(Feedback::graph_imported(file))
+}
+
+/// Replaces the current graph with the one in the adjacency matrix, names can
+/// come from an optional nodes CSV. It is saved as JSON next to the matrix
+fn import_adjacency_matrix(graph: &mut Graph, file: &mut PathBuf) -> Result<String, GraphError> {
+    println!("{}", Feedback::read_matrix_csv());
+    let matrix_path = read_path("csv", &["csv"], false).unwrap();
+
+    println!("\n{}", Feedback::read_nodes_csv());
+    let nodes_path = read_path("csv", &["csv"], true);
+
+    let is_directed = read_confirmation(&Feedback::read_is_directed());
+    let is_weighted = read_confirmation(&Feedback::read_is_weighted());
+
+    let matrix = fs::read_to_string(&matrix_path)?;
+    let nodes = match &nodes_path {
+        Some(nodes_path) => Some(fs::read_to_string(nodes_path)?),
+        None => None,
+    };
+
+    *graph = csv::from_adjacency_matrix(&matrix, nodes.as_deref(), is_directed, is_weighted)?;
+    *file = read_import_save_path(matrix_path.with_extension("json"));
+
+    Ok(Feedback::graph_imported(file))
+}
+
+/// Where an imported graph is saved, `path` unless that file already exists
+/// and the user doesn't want the graph saved over it, then another is read
+fn read_import_save_path(mut path: PathBuf) -> PathBuf {
+    while path.exists() && !read_confirmation(&Feedback::confirm_save_import_over(&path)) {
+        println!("\n{}", Feedback::read_import_save_path());
+        path = read_file_path();
+
+        while !graph_exporter::can_write(&path) {
+            println!("{}", Feedback::save_format_not_supported());
+            path = read_file_path();
+        }
+    }
+
+    path
+}
+
+/// Where the unsaved changes to the graph saved in `file` are autosaved, e.g.
+/// `campus.json` is recovered from `campus.recovery.json`
+pub fn recovery_path(file: &Path) -> PathBuf {
+    file.with_extension("recovery.json")
+}
+
+/// Writes the graph to its recovery file while it has unsaved changes, the
+/// file is removed once they're saved or discarded
+pub fn write_recovery(graph: &Graph, file: &Path, autosave: bool) {
+    if !autosave {
+        return;
+    }
+
+    if !graph.is_dirty() {
+        remove_recovery(file);
+        return;
+    }
+
+    let recovery = recovery_path(file);
+
+    if let Err(err) = graph.save(&recovery) {
+        println!("{}", Feedback::autosave_error(&recovery, &err));
+    }
+}
+
+pub fn remove_recovery(file: &Path) {
+    let _ = fs::remove_file(recovery_path(file));
+}
+
+/// Asks if the unsaved changes can be discarded, always true when there are
+/// none. Discarding them also removes the recovery file
+pub fn confirm_discard_changes(graph: &Graph, file: &Path) -> bool {
+    if !graph.is_dirty() {
+        return true;
+    }
+
+    let discard = read_confirmation(&Feedback::confirm_discard_changes());
+
+    if discard {
+        remove_recovery(file);
+    }
+
+    discard
+}
+
+/// Offers to restore the unsaved changes autosaved for `file`, the recovery
+/// file is removed when they're not wanted
+pub fn restore_recovery(file: &Path) -> Option<Graph> {
+    let recovery = recovery_path(file);
+
+    if !recovery.exists() {
+        return None;
+    }
+
+    if !read_confirmation(&Feedback::confirm_restore_recovery(&recovery)) {
+        remove_recovery(file);
+        return None;
+    }
+
+    match Graph::load(&recovery) {
+        Ok(mut graph) => {
+            graph.mark_dirty();
+            println!("\n{}", Feedback::recovery_restored());
+
+            Some(graph)
+        }
+        Err(err) => {
+            println!("{}", Feedback::read_graph_file_error(&err));
+            None
         }
-        Err(_) => None,
+    }
+}
+
+fn toggle_autosave(graph: &Graph, file: &Path, autosave: &mut bool) -> RunOptResult {
+    *autosave = !*autosave;
+
+    if *autosave {
+        write_recovery(graph, file, true);
+
+        Ok(Feedback::autosave_enabled(&recovery_path(file)))
+    } else {
+        remove_recovery(file);
+
+        Ok(Feedback::autosave_disabled())
     }
 }
 
 fn show_graph(graph: &Graph) -> RunOptResult {
-    Ok(graph.to_string())
+    Ok(Feedback::format_graph(graph))
 }
 
-fn export_graph(graph: &Graph) -> RunOptResult {
-    match graph_exporter::export_graph(graph) {
+/// Asks if the graph should be exported with `highlight` in red, `subject`
+/// names what is highlighted in the question
+fn offer_highlighted_export(
+    graph: &Graph,
+    highlight: &Graph,
+    file: &Path,
+    subject: &str,
+) -> RunOptResult {
+    if read_confirmation(&Feedback::read_export_highlight(subject)) {
+        export_graph(graph, Some(highlight), file)
+    } else {
+        Ok("".to_string())
+    }
+}
+
+fn export_graph(graph: &Graph, highlight: Option<&Graph>, file: &Path) -> RunOptResult {
+    let backend = read_export_backend(graph);
+    let output = graph_exporter::output_path(file, backend);
+
+    match graph_exporter::export_graph(graph, highlight, &output, backend) {
         Ok(_) => {
             let cwd = env::current_dir().unwrap();
-            let path = Path::new(&cwd).join(DOT_OUTPUT);
+            let path = Path::new(&cwd).join(output);
 
             Ok(Feedback::graph_exported(path.to_str().unwrap()))
         }
-        Err(_) => Err(Feedback::graph_not_exported()),
+        Err(err) => Err(Feedback::graph_not_exported(backend, &err.to_string())),
     }
 }
 
@@ -464,10 +1289,10 @@ fn read_cycle() -> Vec<usize> {
             continue;
         }
 
-        let codes = codes_iter.map(|c| c.unwrap()).collect();
+        let codes: Vec<usize> = codes_iter.map(|c| c.unwrap()).collect();
 
-        if !Graph::is_cycle(&codes) {
-            println!("{}\n", Feedback::invalid_cycle());
+        if let Err(err) = Graph::validate_cycle(&codes) {
+            println!("{}\n", Feedback::graph_error(&err));
             continue;
         }
 
@@ -477,7 +1302,368 @@ fn read_cycle() -> Vec<usize> {
     }
 }
 
+/// Reads the path of a graph file, listing the ones in the current directory.
+/// A name without extension, like `campus`, becomes `campus.json`
+fn read_file_path() -> PathBuf {
+    read_path("json", &graph_importer::SUPPORTED_EXTENSIONS, false).unwrap()
+}
+
+/// Reads a path listing the files with one of `extensions` in the current
+/// directory, `default_extension` is added to names without extension.
+/// Returns `None` when `optional` is set and nothing is typed
+fn read_path(default_extension: &str, extensions: &[&str], optional: bool) -> Option<PathBuf> {
+    println!("{}", Feedback::available_files(&list_files(extensions)));
+
+    loop {
+        println!("{}", Feedback::read_file_path(default_extension, optional));
+
+        let mut path = String::new();
+
+        io::stdin().read_line(&mut path).unwrap();
+        println!("{}", Feedback::value_read(&path, "Arquivo digitado"));
+
+        if path.trim().is_empty() {
+            if optional {
+                break None;
+            }
+
+            println!("{}", Feedback::invalid_file_path());
+            continue;
+        }
+
+        let mut path = PathBuf::from(path.trim());
+
+        if path.extension().is_none() {
+            path.set_extension(default_extension);
+        }
+
+        break Some(path);
+    }
+}
+
+fn list_files(extensions: &[&str]) -> Vec<String> {
+    let Ok(entries) = fs::read_dir(".") else {
+        return Vec::new();
+    };
+
+    let mut files: Vec<String> = entries
+        .filter_map(|entry| entry.ok())
+        .map(|entry| entry.path())
+        .filter(|path| {
+            path.extension()
+                .and_then(|ext| ext.to_str())
+                .is_some_and(|ext| extensions.contains(&ext))
+        })
+        .filter_map(|path| Some(path.file_name()?.to_str()?.to_string()))
+        .collect();
+
+    files.sort();
+    files
+}
+
+fn read_limit<T: FromStr>(text: &str) -> Option<T> {
+    loop {
+        println!("{text}");
+
+        let mut limit = String::new();
+
+        io::stdin().read_line(&mut limit).unwrap();
+        println!("{}", Feedback::value_read(&limit, "Limite digitado"));
+
+        if limit.trim().is_empty() {
+            break None;
+        }
+
+        match limit.trim().parse() {
+            Ok(parsed_limit) => break Some(parsed_limit),
+            Err(_) => {
+                println!("{}", Feedback::invalid_limit());
+                continue;
+            }
+        }
+    }
+}
+
+fn read_csv_mode() -> CsvMode {
+    loop {
+        println!("{}", Feedback::read_csv_mode());
+
+        let mut mode = String::new();
+
+        io::stdin().read_line(&mut mode).unwrap();
+        println!("{}", Feedback::value_read(&mode, "Opção digitada"));
+
+        match mode.trim() {
+            "i" => break CsvMode::ImportLists,
+            "m" => break CsvMode::ImportMatrix,
+            "e" => break CsvMode::ExportLists,
+            "x" => break CsvMode::ExportMatrix,
+            _ => {
+                println!("{}", Feedback::invalid_option());
+                continue;
+            }
+        }
+    }
+}
+
+fn read_attribute_mode() -> AttributeMode {
+    loop {
+        println!("{}", Feedback::read_attribute_mode());
+
+        let mut mode = String::new();
+
+        io::stdin().read_line(&mut mode).unwrap();
+        println!("{}", Feedback::value_read(&mode, "Opção digitada"));
+
+        match mode.trim() {
+            "v" => break AttributeMode::Node,
+            "a" => break AttributeMode::Edge,
+            "c" => break AttributeMode::Query,
+            _ => {
+                println!("{}", Feedback::invalid_option());
+                continue;
+            }
+        }
+    }
+}
+
+fn read_attribute_key(text: &str) -> String {
+    loop {
+        println!("{text}");
+
+        let mut key = String::new();
+
+        io::stdin().read_line(&mut key).unwrap();
+        println!("{}", Feedback::value_read(&key, "Atributo digitado"));
+
+        if key.trim().is_empty() {
+            println!("{}", Feedback::invalid_attribute_key());
+            continue;
+        }
+
+        break key.trim().to_string();
+    }
+}
+
+/// Returns `None` when nothing is typed, which removes the attribute
+fn read_attribute_value() -> Option<String> {
+    println!("{}", Feedback::read_attribute_value());
+
+    let mut value = String::new();
+
+    io::stdin().read_line(&mut value).unwrap();
+    println!("{}", Feedback::value_read(&value, "Valor digitado"));
+
+    Some(value.trim().to_string()).filter(|value| !value.is_empty())
+}
+
+fn read_weight_mode() -> WeightMode {
+    loop {
+        println!("{}", Feedback::read_weight_mode());
+
+        let mut mode = String::new();
+
+        io::stdin().read_line(&mut mode).unwrap();
+        println!("{}", Feedback::value_read(&mode, "Opção digitada"));
+
+        match mode.trim() {
+            "e" => break WeightMode::Edit,
+            "m" => break WeightMode::Scale,
+            "n" => break WeightMode::Remove,
+            _ => {
+                println!("{}", Feedback::invalid_option());
+                continue;
+            }
+        }
+    }
+}
+
+fn read_scale_factor() -> f64 {
+    loop {
+        println!("{}", Feedback::read_scale_factor());
+
+        let mut factor = String::new();
+
+        io::stdin().read_line(&mut factor).unwrap();
+        println!("{}", Feedback::value_read(&factor, "Fator digitado"));
+
+        // Accepts a decimal comma too, e.g. 1,5
+        match factor.trim().replace(',', ".").parse::<f64>() {
+            Ok(parsed_factor) if parsed_factor.is_finite() && parsed_factor > 0.0 => {
+                break parsed_factor;
+            }
+            _ => {
+                println!("{}", Feedback::invalid_scale_factor());
+                continue;
+            }
+        }
+    }
+}
+
+fn read_history_mode() -> HistoryMode {
+    loop {
+        println!("{}", Feedback::read_history_mode());
+
+        let mut mode = String::new();
+
+        io::stdin().read_line(&mut mode).unwrap();
+        println!("{}", Feedback::value_read(&mode, "Opção digitada"));
+
+        match mode.trim() {
+            "d" => break HistoryMode::Undo,
+            "r" => break HistoryMode::Redo,
+            "a" => break HistoryMode::Log,
+            _ => {
+                println!("{}", Feedback::invalid_option());
+                continue;
+            }
+        }
+    }
+}
+
+fn read_node_mode() -> NodeMode {
+    loop {
+        println!("{}", Feedback::read_node_mode());
+
+        let mut mode = String::new();
+
+        io::stdin().read_line(&mut mode).unwrap();
+        println!("{}", Feedback::value_read(&mode, "Opção digitada"));
+
+        match mode.trim() {
+            "a" => break NodeMode::Add,
+            "r" => break NodeMode::Rename,
+            "d" => break NodeMode::Remove,
+            _ => {
+                println!("{}", Feedback::invalid_option());
+                continue;
+            }
+        }
+    }
+}
+
+fn read_cycle_mode() -> CycleMode {
+    loop {
+        println!("{}", Feedback::read_cycle_mode());
+
+        let mut mode = String::new();
+
+        io::stdin().read_line(&mut mode).unwrap();
+        println!("{}", Feedback::value_read(&mode, "Modo digitado"));
+
+        match mode.trim() {
+            "t" => break CycleMode::All,
+            "v" => break CycleMode::Through,
+            "d" => break CycleMode::Verify,
+            _ => {
+                println!("{}", Feedback::invalid_option());
+                continue;
+            }
+        }
+    }
+}
+
+fn read_export_backend(graph: &Graph) -> Backend {
+    loop {
+        println!("{}", Feedback::read_export_backend());
+
+        let mut backend = String::new();
+
+        io::stdin().read_line(&mut backend).unwrap();
+        println!("{}", Feedback::value_read(&backend, "Formato digitado"));
+
+        match backend.trim() {
+            "f" => break Backend::Svg(Layout::ForceDirected),
+            "c" => break Backend::Svg(Layout::Circular),
+            "g" => break Backend::Graphviz(read_dot_options(graph)),
+            _ => {
+                println!("{}", Feedback::invalid_option());
+                continue;
+            }
+        }
+    }
+}
+
+fn read_dot_options(graph: &Graph) -> DotOptions {
+    let format = read_dot_format();
+
+    DotOptions {
+        format,
+        color_by_type: read_confirmation(&Feedback::read_color_by_type()),
+        shape_by_type: read_confirmation(&Feedback::read_shape_by_type()),
+        scale_by_weight: graph.is_weighted()
+            && read_confirmation(&Feedback::read_scale_by_weight()),
+    }
+}
+
+fn read_dot_format() -> DotFormat {
+    loop {
+        println!("{}", Feedback::read_dot_format());
+
+        let mut format = String::new();
+
+        io::stdin().read_line(&mut format).unwrap();
+        println!("{}", Feedback::value_read(&format, "Formato digitado"));
+
+        match format.trim() {
+            "p" => break DotFormat::Png,
+            "s" => break DotFormat::Svg,
+            "d" => break DotFormat::Pdf,
+            _ => {
+                println!("{}", Feedback::invalid_option());
+                continue;
+            }
+        }
+    }
+}
+
+fn read_confirmation(text: &str) -> bool {
+    loop {
+        println!("{text}");
+
+        let mut answer = String::new();
+
+        io::stdin().read_line(&mut answer).unwrap();
+        println!("{}", Feedback::value_read(&answer, "Opção digitada"));
+
+        match answer.trim() {
+            "s" => break true,
+            "n" => break false,
+            _ => {
+                println!("{}", Feedback::invalid_option());
+                continue;
+            }
+        }
+    }
+}
+
+fn read_path_mode() -> PathMode {
+    loop {
+        println!("{}", Feedback::read_path_mode());
+
+        let mut mode = String::new();
+
+        io::stdin().read_line(&mut mode).unwrap();
+        println!("{}", Feedback::value_read(&mode, "Modo digitado"));
+
+        match mode.trim() {
+            "p" => break PathMode::Depth,
+            "l" => break PathMode::Breadth,
+            _ => {
+                println!("{}", Feedback::invalid_option());
+                continue;
+            }
+        }
+    }
+}
+
+/// Reads codes until one of a node of the graph, fails when it has no nodes,
+/// e.g. one imported from an empty file
 fn read_node(graph: &Graph) -> Result<&Node, String> {
+    if graph.size() == 0 {
+        return Err(Feedback::graph_without_nodes());
+    }
+
     loop {
         let code = read_code();
 
@@ -491,7 +1677,7 @@ fn read_node(graph: &Graph) -> Result<&Node, String> {
     }
 }
 
-fn read_weight() -> u32 {
+fn read_weight() -> Weight {
     loop {
         println!("{}", Feedback::read_weight());
 
@@ -500,11 +1686,12 @@ fn read_weight() -> u32 {
         io::stdin().read_line(&mut weight).unwrap();
         println!("{}", Feedback::value_read(&weight, "Peso digitado"));
 
-        match weight.trim().parse() {
-            Ok(parsed_weight) => {
+        // Accepts a decimal comma too, e.g. 2,5
+        match weight.trim().replace(',', ".").parse::<Weight>() {
+            Ok(parsed_weight) if !parsed_weight.is_zero() => {
                 break parsed_weight;
             }
-            Err(_) => {
+            _ => {
                 println!("{}", Feedback::invalid_weight());
                 continue;
             }
@@ -516,10 +1703,11 @@ fn format_available_nodes(graph: &Graph) -> String {
     print!("{}", Feedback::available_nodes());
 
     let mut string = String::new();
-    for node in graph.nodes.iter() {
+    for node in graph.nodes() {
         string = format!(
-            "{string}\n{} - {node}",
-            node.code.to_string().magenta().bold()
+            "{string}\n{} - {}",
+            node.code.to_string().magenta().bold(),
+            Feedback::format_node(node)
         );
     }
 
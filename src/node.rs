@@ -1,24 +1,40 @@
-use colored::*;
 use serde::{Deserialize, Serialize};
-use std::fmt::Display;
+use std::{collections::BTreeMap, fmt::Display};
+
+/// Free-form metadata of nodes and edges, like capacity or opening hours,
+/// sorted by key
+pub type Attributes = BTreeMap<String, String>;
 
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct Node {
     pub code: usize,
     pub name: String,
     pub local_type: String,
+    #[serde(default, skip_serializing_if = "Attributes::is_empty")]
+    pub attributes: Attributes,
 }
 
 impl Display for Node {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         write!(
             f,
-            "{}Código: {}, Nome: {}, Tipo do local: {}{}",
-            "|".green(),
-            self.code.to_string().cyan(),
-            self.name.cyan(),
-            self.local_type.cyan(),
-            "|".green()
-        )
+            "Código: {}, Nome: {}, Tipo do local: {}",
+            self.code, self.name, self.local_type
+        )?;
+
+        if !self.attributes.is_empty() {
+            write!(f, ", {}", format_attributes(&self.attributes))?;
+        }
+
+        Ok(())
     }
 }
+
+/// `chave: valor` pairs separated by commas
+pub fn format_attributes(attributes: &Attributes) -> String {
+    attributes
+        .iter()
+        .map(|(key, value)| format!("{key}: {value}"))
+        .collect::<Vec<_>>()
+        .join(", ")
+}
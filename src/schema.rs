@@ -0,0 +1,113 @@
+use serde_json::{Map, Value};
+
+use crate::error::GraphError;
+
+/// Version written in the `version` field of the saved JSON
+pub const CURRENT_VERSION: u64 = 1;
+
+/// Version of the JSON document, files saved before the field existed are
+/// version 0
+pub fn version_of(document: &Value) -> Result<u64, GraphError> {
+    let object = as_object(document)?;
+
+    match object.get("version") {
+        None => Ok(0),
+        Some(Value::Number(version)) => version
+            .as_u64()
+            .ok_or_else(|| invalid(&format!("versão inválida: {version}"))),
+        Some(version) => Err(invalid(&format!("versão inválida: {version}"))),
+    }
+}
+
+/// Updates the document from `version` to `CURRENT_VERSION`, one version at
+/// a time
+pub fn migrate(mut document: Value, version: u64) -> Result<Value, GraphError> {
+    if version > CURRENT_VERSION {
+        return Err(GraphError::UnsupportedVersion(version));
+    }
+
+    for from in version..CURRENT_VERSION {
+        let object = as_object_mut(&mut document)?;
+
+        match from {
+            0 => migrate_v0(object),
+            _ => unreachable!("every version before the current one has a migration"),
+        }
+
+        object.insert("version".to_string(), Value::from(from + 1));
+    }
+
+    Ok(document)
+}
+
+/// Version 0 has a `size` field, always equal to the number of nodes, and
+/// `is_directed` only exists in files saved after directed graphs were added
+fn migrate_v0(object: &mut Map<String, Value>) {
+    object.remove("size");
+    object.entry("is_directed").or_insert(Value::Bool(false));
+}
+
+fn as_object(document: &Value) -> Result<&Map<String, Value>, GraphError> {
+    document
+        .as_object()
+        .ok_or_else(|| invalid("o grafo precisa ser um objeto JSON"))
+}
+
+fn as_object_mut(document: &mut Value) -> Result<&mut Map<String, Value>, GraphError> {
+    document
+        .as_object_mut()
+        .ok_or_else(|| invalid("o grafo precisa ser um objeto JSON"))
+}
+
+fn invalid(message: &str) -> GraphError {
+    GraphError::Parse {
+        line: 0,
+        column: 0,
+        message: message.to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+
+    #[test]
+    fn migrates_unversioned_files() {
+        let document = json!({
+            "is_weighted": false,
+            "size": 1,
+            "nodes": [{"code": 1, "name": "A", "local_type": ""}],
+            "edges": []
+        });
+
+        assert_eq!(version_of(&document).unwrap(), 0);
+
+        let migrated = migrate(document, 0).unwrap();
+
+        assert_eq!(
+            migrated,
+            json!({
+                "version": CURRENT_VERSION,
+                "is_weighted": false,
+                "is_directed": false,
+                "nodes": [{"code": 1, "name": "A", "local_type": ""}],
+                "edges": []
+            })
+        );
+        assert_eq!(version_of(&migrated).unwrap(), CURRENT_VERSION);
+    }
+
+    #[test]
+    fn keeps_is_directed_and_rejects_newer_versions() {
+        let document = json!({"is_weighted": true, "is_directed": true, "nodes": [], "edges": []});
+
+        assert_eq!(migrate(document, 0).unwrap()["is_directed"], json!(true));
+        assert!(matches!(
+            migrate(json!({}), CURRENT_VERSION + 1),
+            Err(GraphError::UnsupportedVersion(_))
+        ));
+        assert!(version_of(&json!({"version": "1"})).is_err());
+    }
+}
@@ -0,0 +1,308 @@
+use std::{collections::HashMap, f64::consts::PI};
+
+use crate::{graph::Graph, node::Node, xml::escape};
+
+const NODE_RADIUS: f64 = 22.0;
+const MARGIN: f64 = 80.0;
+const FORCE_ITERATIONS: usize = 200;
+
+/// How the vertices are placed on the canvas
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum Layout {
+    /// Vertices evenly spaced on a circle, in the order they were created
+    Circular,
+    /// Fruchterman-Reingold: connected vertices attract and every pair repels,
+    /// starting from the circular layout so the result is always the same
+    ForceDirected,
+}
+
+#[derive(Clone, Copy)]
+struct Point {
+    x: f64,
+    y: f64,
+}
+
+/// Renders the graph as an SVG document, edges that are also in `highlight`
+/// are drawn in red
+pub fn render_svg(graph: &Graph, highlight: Option<&Graph>, layout: Layout) -> String {
+    let nodes: Vec<&Node> = graph.nodes().collect();
+    let side = canvas_side(nodes.len());
+
+    let positions = match layout {
+        Layout::Circular => circular_layout(nodes.len(), side),
+        Layout::ForceDirected => force_directed_layout(graph, &nodes, side),
+    };
+
+    let indices = indices_by_code(&nodes);
+    let position_of = |code: usize| positions[indices[&code]];
+
+    let mut svg = format!(
+        "<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"{side:.0}\" height=\"{side:.0}\" viewBox=\"0 0 {side:.0} {side:.0}\" font-family=\"sans-serif\" font-size=\"12\">\n"
+    );
+
+    if graph.is_directed() {
+        svg.push_str(
+            "    <defs>\n        <marker id=\"arrow\" viewBox=\"0 0 10 10\" refX=\"10\" refY=\"5\" markerWidth=\"8\" markerHeight=\"8\" orient=\"auto\">\n            <path d=\"M 0 0 L 10 5 L 0 10 z\" fill=\"context-stroke\"/>\n        </marker>\n    </defs>\n",
+        );
+    }
+
+    svg.push_str("    <rect width=\"100%\" height=\"100%\" fill=\"white\"/>\n");
+
+    for edge in graph.edges() {
+        let from = position_of(edge.from);
+        let to = position_of(edge.to);
+
+        let (color, width) =
+            if highlight.is_some_and(|h| h.find_edge_by_from_to(edge.from, edge.to).is_some()) {
+                ("red", 3.0)
+            } else {
+                ("black", 1.5)
+            };
+
+        let marker = if graph.is_directed() {
+            " marker-end=\"url(#arrow)\""
+        } else {
+            ""
+        };
+
+        // Directed graphs can have both `a -> b` and `b -> a`, those are
+        // drawn as curves so they don't overlap
+        let curved =
+            graph.is_directed() && graph.find_edge_by_from_to(edge.to, edge.from).is_some();
+
+        let (path, label) = if edge.from == edge.to {
+            loop_path(from)
+        } else {
+            edge_path(from, to, curved)
+        };
+
+        svg.push_str(&format!(
+            "    <path d=\"{path}\" fill=\"none\" stroke=\"{color}\" stroke-width=\"{width}\"{marker}/>\n"
+        ));
+
+        if graph.is_weighted() {
+            svg.push_str(&format!(
+                "    <text x=\"{:.1}\" y=\"{:.1}\" text-anchor=\"middle\" fill=\"{color}\" stroke=\"white\" stroke-width=\"3\" paint-order=\"stroke\">{}</text>\n",
+                label.x, label.y, edge.weight
+            ));
+        }
+    }
+
+    for (node, position) in nodes.iter().zip(&positions) {
+        svg.push_str(&format!(
+            "    <circle cx=\"{:.1}\" cy=\"{:.1}\" r=\"{NODE_RADIUS}\" fill=\"white\" stroke=\"black\" stroke-width=\"1.5\"/>\n",
+            position.x, position.y
+        ));
+        svg.push_str(&format!(
+            "    <text x=\"{:.1}\" y=\"{:.1}\" text-anchor=\"middle\" dominant-baseline=\"central\" font-weight=\"bold\">{}</text>\n",
+            position.x, position.y, node.code
+        ));
+        svg.push_str(&format!(
+            "    <text x=\"{:.1}\" y=\"{:.1}\" text-anchor=\"middle\">{}</text>\n",
+            position.x,
+            position.y + NODE_RADIUS + 14.0,
+            escape(&node.name)
+        ));
+    }
+
+    svg.push_str("</svg>\n");
+
+    svg
+}
+
+fn canvas_side(node_count: usize) -> f64 {
+    (160.0 * (node_count as f64).sqrt()).max(300.0) + 2.0 * MARGIN
+}
+
+fn circular_layout(node_count: usize, side: f64) -> Vec<Point> {
+    let center = side / 2.0;
+
+    if node_count == 1 {
+        return vec![Point {
+            x: center,
+            y: center,
+        }];
+    }
+
+    let radius = center - MARGIN;
+
+    (0..node_count)
+        .map(|i| {
+            // Starts at the top and goes clockwise
+            let angle = 2.0 * PI * i as f64 / node_count as f64 - PI / 2.0;
+
+            Point {
+                x: center + radius * angle.cos(),
+                y: center + radius * angle.sin(),
+            }
+        })
+        .collect()
+}
+
+/// Position of each node in `nodes` by its code
+fn indices_by_code(nodes: &[&Node]) -> HashMap<usize, usize> {
+    nodes
+        .iter()
+        .enumerate()
+        .map(|(index, node)| (node.code, index))
+        .collect()
+}
+
+fn force_directed_layout(graph: &Graph, nodes: &[&Node], side: f64) -> Vec<Point> {
+    let mut positions = circular_layout(nodes.len(), side);
+
+    if nodes.len() < 2 {
+        return positions;
+    }
+
+    let area = (side - 2.0 * MARGIN).powi(2);
+    let k = (area / nodes.len() as f64).sqrt();
+
+    let indices = indices_by_code(nodes);
+    let edges: Vec<(usize, usize)> = graph
+        .edges()
+        .filter(|edge| edge.from != edge.to)
+        .map(|edge| (indices[&edge.from], indices[&edge.to]))
+        .collect();
+
+    for iteration in 0..FORCE_ITERATIONS {
+        let temperature = side / 10.0 * (1.0 - iteration as f64 / FORCE_ITERATIONS as f64);
+        let mut displacement = vec![Point { x: 0.0, y: 0.0 }; nodes.len()];
+
+        for i in 0..nodes.len() {
+            for j in (i + 1)..nodes.len() {
+                let (dx, dy, distance) = difference(positions[i], positions[j]);
+                let force = k * k / distance;
+
+                displacement[i].x += dx / distance * force;
+                displacement[i].y += dy / distance * force;
+                displacement[j].x -= dx / distance * force;
+                displacement[j].y -= dy / distance * force;
+            }
+        }
+
+        for &(from, to) in &edges {
+            let (dx, dy, distance) = difference(positions[from], positions[to]);
+            let force = distance * distance / k;
+
+            displacement[from].x -= dx / distance * force;
+            displacement[from].y -= dy / distance * force;
+            displacement[to].x += dx / distance * force;
+            displacement[to].y += dy / distance * force;
+        }
+
+        for (position, displacement) in positions.iter_mut().zip(&displacement) {
+            let length = displacement.x.hypot(displacement.y).max(0.01);
+            let step = length.min(temperature);
+
+            position.x += displacement.x / length * step;
+            position.y += displacement.y / length * step;
+        }
+    }
+
+    fit(&mut positions, side);
+
+    positions
+}
+
+/// Scales and moves the points so they fill the canvas, keeping the proportions
+fn fit(positions: &mut [Point], side: f64) {
+    let min_x = positions.iter().map(|p| p.x).fold(f64::INFINITY, f64::min);
+    let max_x = positions
+        .iter()
+        .map(|p| p.x)
+        .fold(f64::NEG_INFINITY, f64::max);
+    let min_y = positions.iter().map(|p| p.y).fold(f64::INFINITY, f64::min);
+    let max_y = positions
+        .iter()
+        .map(|p| p.y)
+        .fold(f64::NEG_INFINITY, f64::max);
+
+    let available = side - 2.0 * MARGIN;
+    let scale = available / (max_x - min_x).max(max_y - min_y).max(0.01);
+    // Centers the axis that doesn't use the whole canvas
+    let offset_x = MARGIN + (available - (max_x - min_x) * scale) / 2.0;
+    let offset_y = MARGIN + (available - (max_y - min_y) * scale) / 2.0;
+
+    for position in positions {
+        position.x = offset_x + (position.x - min_x) * scale;
+        position.y = offset_y + (position.y - min_y) * scale;
+    }
+}
+
+/// Returns the vector from `b` to `a` and its length, never 0 so it can be
+/// used as a divisor
+fn difference(a: Point, b: Point) -> (f64, f64, f64) {
+    let (dx, dy) = (a.x - b.x, a.y - b.y);
+
+    (dx, dy, dx.hypot(dy).max(0.01))
+}
+
+/// Path between the borders of the two vertices and the position of its label
+fn edge_path(from: Point, to: Point, curved: bool) -> (String, Point) {
+    let (dx, dy, distance) = difference(to, from);
+    let (ux, uy) = (dx / distance, dy / distance);
+
+    let start = Point {
+        x: from.x + ux * NODE_RADIUS,
+        y: from.y + uy * NODE_RADIUS,
+    };
+    let end = Point {
+        x: to.x - ux * NODE_RADIUS,
+        y: to.y - uy * NODE_RADIUS,
+    };
+    let middle = Point {
+        x: (start.x + end.x) / 2.0,
+        y: (start.y + end.y) / 2.0,
+    };
+
+    if !curved {
+        let path = format!(
+            "M {:.1} {:.1} L {:.1} {:.1}",
+            start.x, start.y, end.x, end.y
+        );
+
+        return (path, middle);
+    }
+
+    // Bends to the left of the direction, so the opposite edge bends the other way
+    let offset = 30.0;
+    let control = Point {
+        x: middle.x + uy * offset,
+        y: middle.y - ux * offset,
+    };
+    let path = format!(
+        "M {:.1} {:.1} Q {:.1} {:.1} {:.1} {:.1}",
+        start.x, start.y, control.x, control.y, end.x, end.y
+    );
+    let label = Point {
+        x: middle.x + uy * offset / 2.0,
+        y: middle.y - ux * offset / 2.0,
+    };
+
+    (path, label)
+}
+
+/// Loop drawn above the vertex, used for buckles
+fn loop_path(center: Point) -> (String, Point) {
+    let top = center.y - NODE_RADIUS;
+    let path = format!(
+        "M {:.1} {:.1} C {:.1} {:.1} {:.1} {:.1} {:.1} {:.1}",
+        center.x - 10.0,
+        top + 2.0,
+        center.x - 35.0,
+        top - 45.0,
+        center.x + 35.0,
+        top - 45.0,
+        center.x + 10.0,
+        top + 2.0
+    );
+
+    (
+        path,
+        Point {
+            x: center.x,
+            y: top - 38.0,
+        },
+    )
+}
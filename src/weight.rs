@@ -0,0 +1,187 @@
+use serde::{Deserialize, Serialize};
+use std::{
+    cmp::Ordering,
+    fmt::Display,
+    iter::Sum,
+    ops::{Add, AddAssign},
+    str::FromStr,
+};
+
+use crate::error::GraphError;
+
+/// Edge weight, integer or floating-point and possibly negative. Integers
+/// are kept apart so they're saved as JSON integers, like before floats were
+/// supported. Floats are always finite
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum Weight {
+    Integer(i64),
+    Float(f64),
+}
+
+impl Weight {
+    pub const ZERO: Weight = Weight::Integer(0);
+    /// Weight of the edges of unweighted graphs
+    pub const ONE: Weight = Weight::Integer(1);
+
+    pub fn as_f64(self) -> f64 {
+        match self {
+            Weight::Integer(weight) => weight as f64,
+            Weight::Float(weight) => weight,
+        }
+    }
+
+    pub fn is_zero(self) -> bool {
+        self.as_f64() == 0.0
+    }
+
+    pub fn is_negative(self) -> bool {
+        self.as_f64() < 0.0
+    }
+
+    /// Multiplies the weight by `factor`, integers stay integers while the
+    /// result is integral. `None` when the result overflows or becomes 0
+    pub fn scale(self, factor: f64) -> Option<Weight> {
+        let scaled = self.as_f64() * factor;
+
+        if !scaled.is_finite() || scaled == 0.0 {
+            return None;
+        }
+
+        match self {
+            Weight::Integer(_) if scaled.fract() == 0.0 && scaled.abs() < i64::MAX as f64 => {
+                Some(Weight::Integer(scaled as i64))
+            }
+            _ => Some(Weight::Float(scaled)),
+        }
+    }
+}
+
+impl From<i64> for Weight {
+    fn from(weight: i64) -> Self {
+        Weight::Integer(weight)
+    }
+}
+
+impl Add for Weight {
+    type Output = Weight;
+
+    /// Integers that overflow are added as floats
+    fn add(self, other: Weight) -> Weight {
+        match (self, other) {
+            (Weight::Integer(a), Weight::Integer(b)) => match a.checked_add(b) {
+                Some(sum) => Weight::Integer(sum),
+                None => Weight::Float(a as f64 + b as f64),
+            },
+            (a, b) => Weight::Float(a.as_f64() + b.as_f64()),
+        }
+    }
+}
+
+impl AddAssign for Weight {
+    fn add_assign(&mut self, other: Weight) {
+        *self = *self + other;
+    }
+}
+
+impl Sum for Weight {
+    fn sum<I: Iterator<Item = Weight>>(iter: I) -> Weight {
+        iter.fold(Weight::ZERO, Add::add)
+    }
+}
+
+/// Integers are compared exactly, mixed with floats by their value, so
+/// `Integer(2)` and `Float(2.0)` are equal
+impl Ord for Weight {
+    fn cmp(&self, other: &Self) -> Ordering {
+        match (self, other) {
+            (Weight::Integer(a), Weight::Integer(b)) => a.cmp(b),
+            (a, b) => a.as_f64().total_cmp(&b.as_f64()),
+        }
+    }
+}
+
+impl PartialOrd for Weight {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl PartialEq for Weight {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl Eq for Weight {}
+
+impl Display for Weight {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Weight::Integer(weight) => write!(f, "{weight}"),
+            Weight::Float(weight) => write!(f, "{weight}"),
+        }
+    }
+}
+
+/// Reads an integer, otherwise a finite float, e.g. `5`, `-3` or `2.5`
+impl FromStr for Weight {
+    type Err = GraphError;
+
+    fn from_str(text: &str) -> Result<Self, Self::Err> {
+        let text = text.trim();
+
+        if let Ok(weight) = text.parse() {
+            return Ok(Weight::Integer(weight));
+        }
+
+        match text.parse::<f64>() {
+            Ok(weight) if weight.is_finite() => Ok(Weight::Float(weight)),
+            _ => Err(GraphError::InvalidWeight(text.to_string())),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn scale_keeps_integers_only_when_integral() {
+        assert!(matches!(
+            Weight::Integer(5).scale(2.0),
+            Some(Weight::Integer(10))
+        ));
+        assert!(matches!(Weight::Integer(5).scale(0.5), Some(Weight::Float(w)) if w == 2.5));
+        assert!(matches!(Weight::Integer(-5).scale(1.5), Some(Weight::Float(w)) if w == -7.5));
+        assert!(matches!(Weight::Float(2.5).scale(2.0), Some(Weight::Float(w)) if w == 5.0));
+    }
+
+    #[test]
+    fn scale_fails_when_out_of_range() {
+        assert_eq!(Weight::Float(f64::MAX).scale(2.0), None);
+        assert_eq!(
+            Weight::Float(f64::MIN_POSITIVE).scale(f64::MIN_POSITIVE),
+            None
+        );
+    }
+
+    #[test]
+    fn integers_and_floats_compare_by_value() {
+        assert_eq!(Weight::Integer(2), Weight::Float(2.0));
+        assert!(Weight::Integer(-3) < Weight::Float(2.5));
+        assert_eq!(
+            Weight::Integer(i64::MAX) + Weight::ONE,
+            Weight::Float(i64::MAX as f64 + 1.0)
+        );
+    }
+
+    #[test]
+    fn parses_integers_floats_and_negatives() {
+        assert!(matches!("5".parse(), Ok(Weight::Integer(5))));
+        assert!(matches!(" -3 ".parse(), Ok(Weight::Integer(-3))));
+        assert!(matches!("2.5".parse(), Ok(Weight::Float(w)) if w == 2.5));
+        assert!("inf".parse::<Weight>().is_err());
+        assert!("abc".parse::<Weight>().is_err());
+    }
+}
@@ -0,0 +1,342 @@
+//! Minimal XML reader and writer helpers for the GraphML and GEXF formats.
+//! Supports elements, attributes, text, comments, CDATA and the standard
+//! entities, DTDs and processing instructions are skipped.
+
+use std::{iter::Peekable, str::Chars};
+
+use crate::error::GraphError;
+
+#[derive(Debug, Default)]
+pub(crate) struct Element {
+    pub name: String,
+    pub attributes: Vec<(String, String)>,
+    pub children: Vec<Element>,
+    pub text: String,
+    pub line: usize,
+    pub column: usize,
+}
+
+impl Element {
+    /// Name without the namespace prefix, `y:ShapeNode` becomes `ShapeNode`
+    pub fn local_name(&self) -> &str {
+        local_name(&self.name)
+    }
+
+    pub fn attribute(&self, name: &str) -> Option<&str> {
+        self.attributes
+            .iter()
+            .find(|(key, _)| local_name(key) == name)
+            .map(|(_, value)| value.as_str())
+    }
+
+    /// Like `attribute`, but missing attributes are a parse error
+    pub fn required_attribute(&self, name: &str) -> Result<&str, GraphError> {
+        self.attribute(name).ok_or_else(|| {
+            self.error(&format!(
+                "o elemento <{}> precisa do atributo {name}",
+                self.name
+            ))
+        })
+    }
+
+    pub fn children_named<'a>(&'a self, name: &'a str) -> impl Iterator<Item = &'a Element> {
+        self.children
+            .iter()
+            .filter(move |child| child.local_name() == name)
+    }
+
+    pub fn child(&self, name: &str) -> Option<&Element> {
+        self.children
+            .iter()
+            .find(|child| child.local_name() == name)
+    }
+
+    pub fn error(&self, message: &str) -> GraphError {
+        GraphError::Parse {
+            line: self.line,
+            column: self.column,
+            message: message.to_string(),
+        }
+    }
+}
+
+fn local_name(name: &str) -> &str {
+    name.rsplit(':').next().unwrap_or(name)
+}
+
+/// Parses the document and returns its root element
+pub(crate) fn parse(source: &str) -> Result<Element, GraphError> {
+    let mut reader = Reader {
+        chars: source.chars().peekable(),
+        line: 1,
+        column: 1,
+    };
+
+    reader.skip_prolog()?;
+
+    let root = match reader.peek() {
+        Some('<') => reader.parse_element()?,
+        _ => return Err(reader.error("esperado o elemento raiz")),
+    };
+
+    reader.skip_misc()?;
+
+    if reader.peek().is_some() {
+        return Err(reader.error("conteúdo após o elemento raiz"));
+    }
+
+    Ok(root)
+}
+
+pub(crate) fn escape(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+struct Reader<'a> {
+    chars: Peekable<Chars<'a>>,
+    line: usize,
+    column: usize,
+}
+
+impl Reader<'_> {
+    fn peek(&mut self) -> Option<char> {
+        self.chars.peek().copied()
+    }
+
+    fn advance(&mut self) -> Option<char> {
+        let c = self.chars.next();
+
+        if c == Some('\n') {
+            self.line += 1;
+            self.column = 1;
+        } else {
+            self.column += 1;
+        }
+
+        c
+    }
+
+    fn error(&self, message: &str) -> GraphError {
+        GraphError::Parse {
+            line: self.line,
+            column: self.column,
+            message: message.to_string(),
+        }
+    }
+
+    fn starts_with(&self, prefix: &str) -> bool {
+        self.chars.clone().take(prefix.len()).eq(prefix.chars())
+    }
+
+    fn expect(&mut self, text: &str) -> Result<(), GraphError> {
+        if !self.starts_with(text) {
+            return Err(self.error(&format!("esperado {text}")));
+        }
+
+        for _ in text.chars() {
+            self.advance();
+        }
+
+        Ok(())
+    }
+
+    fn skip_whitespace(&mut self) {
+        while self.peek().is_some_and(char::is_whitespace) {
+            self.advance();
+        }
+    }
+
+    /// Skips everything until `end`, inclusive
+    fn skip_until(&mut self, end: &str) -> Result<(), GraphError> {
+        while !self.starts_with(end) {
+            if self.advance().is_none() {
+                return Err(self.error(&format!("esperado {end}")));
+            }
+        }
+
+        self.expect(end)
+    }
+
+    fn skip_prolog(&mut self) -> Result<(), GraphError> {
+        // Byte order mark
+        if self.peek() == Some('\u{feff}') {
+            self.advance();
+        }
+
+        self.skip_misc()?;
+
+        if self.starts_with("<!DOCTYPE") {
+            self.skip_until(">")?;
+            self.skip_misc()?;
+        }
+
+        Ok(())
+    }
+
+    /// Whitespace, comments and processing instructions
+    fn skip_misc(&mut self) -> Result<(), GraphError> {
+        loop {
+            self.skip_whitespace();
+
+            if self.starts_with("<?") {
+                self.skip_until("?>")?;
+            } else if self.starts_with("<!--") {
+                self.skip_until("-->")?;
+            } else {
+                return Ok(());
+            }
+        }
+    }
+
+    fn parse_name(&mut self) -> Result<String, GraphError> {
+        let mut name = String::new();
+
+        while self
+            .peek()
+            .is_some_and(|c| c.is_alphanumeric() || matches!(c, '_' | '-' | '.' | ':'))
+        {
+            name.push(self.advance().unwrap());
+        }
+
+        if name.is_empty() {
+            return Err(self.error("esperado um nome"));
+        }
+
+        Ok(name)
+    }
+
+    fn parse_element(&mut self) -> Result<Element, GraphError> {
+        let mut element = Element {
+            line: self.line,
+            column: self.column,
+            ..Default::default()
+        };
+
+        self.expect("<")?;
+        element.name = self.parse_name()?;
+
+        loop {
+            self.skip_whitespace();
+
+            match self.peek() {
+                Some('/') => {
+                    self.expect("/>")?;
+                    return Ok(element);
+                }
+                Some('>') => {
+                    self.advance();
+                    break;
+                }
+                Some(_) => {
+                    let key = self.parse_name()?;
+                    self.skip_whitespace();
+                    self.expect("=")?;
+                    self.skip_whitespace();
+
+                    let quote = match self.advance() {
+                        Some(quote @ ('"' | '\'')) => quote,
+                        _ => return Err(self.error("esperado aspas no valor do atributo")),
+                    };
+
+                    let mut value = String::new();
+
+                    loop {
+                        match self.advance() {
+                            Some(c) if c == quote => break,
+                            Some('&') => value.push_str(&self.parse_entity()?),
+                            Some(c) => value.push(c),
+                            None => return Err(self.error("valor de atributo não fechado")),
+                        }
+                    }
+
+                    element.attributes.push((key, value));
+                }
+                None => return Err(self.error("fim inesperado do arquivo")),
+            }
+        }
+
+        loop {
+            if self.starts_with("</") {
+                self.expect("</")?;
+                let name = self.parse_name()?;
+
+                if name != element.name {
+                    return Err(self.error(&format!(
+                        "esperado </{}>, encontrado </{name}>",
+                        element.name
+                    )));
+                }
+
+                self.skip_whitespace();
+                self.expect(">")?;
+
+                return Ok(element);
+            }
+
+            if self.starts_with("<!--") {
+                self.skip_until("-->")?;
+            } else if self.starts_with("<![CDATA[") {
+                self.expect("<![CDATA[")?;
+
+                while !self.starts_with("]]>") {
+                    match self.advance() {
+                        Some(c) => element.text.push(c),
+                        None => return Err(self.error("esperado ]]>")),
+                    }
+                }
+
+                self.expect("]]>")?;
+            } else if self.starts_with("<?") {
+                self.skip_until("?>")?;
+            } else {
+                match self.peek() {
+                    Some('<') => element.children.push(self.parse_element()?),
+                    Some('&') => {
+                        self.advance();
+                        let entity = self.parse_entity()?;
+                        element.text.push_str(&entity);
+                    }
+                    Some(_) => element.text.push(self.advance().unwrap()),
+                    None => {
+                        return Err(self.error(&format!("esperado </{}>", element.name)));
+                    }
+                }
+            }
+        }
+    }
+
+    /// Reads the entity after `&`, up to and including the `;`
+    fn parse_entity(&mut self) -> Result<String, GraphError> {
+        let mut entity = String::new();
+
+        loop {
+            match self.advance() {
+                Some(';') => break,
+                Some(c) if entity.len() < 10 => entity.push(c),
+                _ => return Err(self.error("entidade inválida")),
+            }
+        }
+
+        let character = match entity.as_str() {
+            "amp" => Some('&'),
+            "lt" => Some('<'),
+            "gt" => Some('>'),
+            "quot" => Some('"'),
+            "apos" => Some('\''),
+            _ => match entity.strip_prefix("#x").or(entity.strip_prefix("#X")) {
+                Some(hex) => u32::from_str_radix(hex, 16).ok().and_then(char::from_u32),
+                None => entity
+                    .strip_prefix('#')
+                    .and_then(|decimal| decimal.parse().ok())
+                    .and_then(char::from_u32),
+            },
+        };
+
+        character
+            .map(String::from)
+            .ok_or_else(|| self.error(&format!("entidade desconhecida: &{entity};")))
+    }
+}
@@ -0,0 +1,114 @@
+use std::{
+    fs,
+    path::{Path, PathBuf},
+    process::{Command, Output},
+};
+
+const GRAPH: &str = r#"{
+    "version": 1,
+    "is_weighted": true,
+    "is_directed": true,
+    "nodes": [
+        {"code": 1, "name": "A", "local_type": ""},
+        {"code": 2, "name": "B", "local_type": ""},
+        {"code": 3, "name": "C", "local_type": ""}
+    ],
+    "edges": [{"from": 1, "to": 2, "weight": 3}]
+}"#;
+
+/// Directory of the test with a copy of `GRAPH` in `graph.json`
+fn setup(test: &str) -> PathBuf {
+    let dir = std::env::temp_dir().join(format!("n1_project-{}-{test}", std::process::id()));
+    fs::create_dir_all(&dir).unwrap();
+    fs::write(dir.join("graph.json"), GRAPH).unwrap();
+
+    dir
+}
+
+fn run(dir: &Path, args: &[&str]) -> Output {
+    Command::new(env!("CARGO_BIN_EXE_n1_project"))
+        .current_dir(dir)
+        .args(args)
+        .output()
+        .unwrap()
+}
+
+#[test]
+fn checks_exit_with_0_or_1() {
+    let dir = setup("checks");
+
+    let found = run(&dir, &["cost", "graph.json", "1", "2"]);
+    assert_eq!(found.status.code(), Some(0));
+    assert!(String::from_utf8_lossy(&found.stdout).contains('3'));
+
+    assert_eq!(
+        run(&dir, &["path", "graph.json", "2", "1"]).status.code(),
+        Some(1)
+    );
+    assert_eq!(
+        run(&dir, &["adjacent", "graph.json", "1", "2"])
+            .status
+            .code(),
+        Some(0)
+    );
+    assert_eq!(
+        run(&dir, &["adjacent", "graph.json", "1", "3"])
+            .status
+            .code(),
+        Some(1)
+    );
+    assert_eq!(
+        run(&dir, &["complete", "graph.json"]).status.code(),
+        Some(1)
+    );
+
+    fs::remove_dir_all(dir).unwrap();
+}
+
+#[test]
+fn errors_exit_with_2() {
+    let dir = setup("errors");
+
+    let unknown = run(&dir, &["unknown"]);
+    assert_eq!(unknown.status.code(), Some(2));
+    assert!(!unknown.stderr.is_empty());
+
+    assert_eq!(
+        run(&dir, &["cost", "graph.json", "1"]).status.code(),
+        Some(2)
+    );
+    assert_eq!(
+        run(&dir, &["cost", "missing.json", "1", "2"]).status.code(),
+        Some(2)
+    );
+    assert_eq!(
+        run(&dir, &["cost", "graph.json", "1", "9"]).status.code(),
+        Some(2)
+    );
+    assert_eq!(
+        run(&dir, &["add-edge", "graph.json", "1", "2"])
+            .status
+            .code(),
+        Some(2)
+    );
+
+    fs::remove_dir_all(dir).unwrap();
+}
+
+#[test]
+fn add_edge_saves_the_graph() {
+    let dir = setup("add-edge");
+
+    assert_eq!(
+        run(&dir, &["add-edge", "graph.json", "2", "3", "4"])
+            .status
+            .code(),
+        Some(0)
+    );
+    assert_eq!(
+        run(&dir, &["cost", "graph.json", "1", "3"]).status.code(),
+        Some(0)
+    );
+
+    fs::remove_dir_all(dir).unwrap();
+}